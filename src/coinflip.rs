@@ -0,0 +1,123 @@
+use alloc::vec::Vec;
+use sha3::{Sha3_512, Digest};
+
+use crate::prng::Yarrow;
+
+/// Domain separation label for the commitment to party A's seed.
+const COMMITMENT_LABEL: &[u8] = b"horizon-coinflip-commitment";
+
+/// A commitment to party A's seed, published before party B chooses its own seed.
+///
+/// It holds the SHA3-512 hash of the seed, so it reveals nothing useful about a seed with enough
+/// entropy (at least 16 random bytes) while binding party A to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commitment(Vec<u8>);
+
+impl Commitment {
+    /// Returns the commitment bytes, to publish or send to party B.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Commits to party A's secret seed, the first step of a commit-reveal coin flip.
+///
+/// The protocol runs as follows: A publishes `commit(seed_a)`; B, having seen the commitment, sends
+/// `seed_b`; A reveals `seed_a`; B checks it with `verify_commitment`; both then call `combine` to
+/// obtain the same generator. A cannot change its seed after seeing B's, and B cannot choose its
+/// seed based on A's, so neither can bias the result alone.
+///
+/// # Parameters
+///
+/// - `seed`: A slice of unsigned 8-bit integers representing party A's secret seed.
+///
+/// # Returns
+///
+/// Returns the commitment to publish.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::coinflip::*;
+/// let commitment = commit(b"party A secret seed");
+/// println!("{}", hex::encode(commitment.as_bytes()));
+/// ```
+pub fn commit(seed: &[u8]) -> Commitment {
+    let mut hasher = Sha3_512::new();
+    hasher.update(COMMITMENT_LABEL);
+    hasher.update(seed);
+    Commitment(hasher.finalize().to_vec())
+}
+
+/// Checks that the seed revealed by party A matches its earlier commitment.
+///
+/// # Parameters
+///
+/// - `commitment`: The commitment published by party A.
+/// - `revealed_a`: The seed party A revealed.
+///
+/// # Returns
+///
+/// Returns `true` if `revealed_a` is the committed seed.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::coinflip::*;
+/// let commitment = commit(b"party A secret seed");
+/// assert!(verify_commitment(&commitment, b"party A secret seed"));
+/// ```
+pub fn verify_commitment(commitment: &Commitment, revealed_a: &[u8]) -> bool {
+    commit(revealed_a) == *commitment
+}
+
+/// Creates the shared generator from both parties' seeds, once party A's seed has been verified.
+///
+/// The seeds are combined with `Yarrow::from_sources`, which hashes each one separately before
+/// hashing the results together, so the generator depends on both contributions and both parties
+/// obtain the same stream.
+///
+/// # Parameters
+///
+/// - `revealed_a`: The seed revealed by party A.
+/// - `seed_b`: The seed contributed by party B.
+///
+/// # Returns
+///
+/// Returns the shared `Yarrow` generator.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::coinflip::*;
+/// let mut shared = combine(b"party A secret seed", b"party B seed");
+/// let heads = shared.generate_random_number() & 1 == 0;
+/// println!("{}", if heads { "heads" } else { "tails" });
+/// ```
+pub fn combine(revealed_a: &[u8], seed_b: &[u8]) -> Yarrow {
+    Yarrow::from_sources(&[revealed_a, seed_b])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_reveal() {
+        let seed_a = b"party A secret seed";
+        let seed_b = b"party B seed";
+        let commitment = commit(seed_a);
+
+        assert!(verify_commitment(&commitment, seed_a));
+        assert_eq!(commitment.as_bytes().len(), 64);
+        assert_eq!(combine(seed_a, seed_b).generate_random_bytes(32), combine(seed_a, seed_b).generate_random_bytes(32));
+        assert_ne!(combine(seed_a, seed_b).generate_random_bytes(32), combine(seed_a, b"other seed").generate_random_bytes(32));
+    }
+
+    #[test]
+    fn test_tampered_reveal() {
+        let commitment = commit(b"party A secret seed");
+        assert!(!verify_commitment(&commitment, b"party A secret seeD"));
+        assert!(!verify_commitment(&commitment, b""));
+    }
+}
@@ -86,7 +86,7 @@ pub(crate) fn decrypt3(cipher_text: &[String], key1: &str, key2: &str, character
 
         if table_2d < table_len && row < table[table_2d].len() {
             if let Some(col) = table[table_2d][row].iter().position(|x| x == c) {
-                plain_text.push_str(&characters.chars().nth(col).unwrap().to_string());
+                plain_text.push(characters.chars().nth(col).unwrap());
             } else {
                 return Err("Error: String not found in table".into());
             }
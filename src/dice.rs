@@ -0,0 +1,251 @@
+use alloc::vec::{IntoIter, Vec};
+use core::iter::Peekable;
+
+use crate::prng::RandomSource;
+use crate::prngerror::PrngError;
+
+/// Maximum number of dice in a single group, e.g. the `1000` of `1000d6`.
+const MAX_DICE: u32 = 1000;
+
+/// The value rolled or added by a single term of a dice expression.
+///
+/// # Variants
+///
+/// - `Dice`: `count` dice with `sides` faces each, e.g. `2d6`.
+/// - `Constant`: A fixed modifier, e.g. the `3` of `2d6+3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Atom {
+    Dice { count: u32, sides: u32 },
+    Constant(u32),
+}
+
+/// A signed, optionally multiplied term of a dice expression, e.g. the `-4d8*2` of `1d20-4d8*2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DiceTerm {
+    negative: bool,
+    atom: Atom,
+    multiplier: i64,
+}
+
+/// A parsed dice expression such as `2d6+1d4-1` or `4d8*2`.
+///
+/// Terms are dice groups (`NdM`, or `dM` for a single die) or integer constants, each optionally
+/// followed by `*` multipliers, joined by `+` or `-`. Whitespace is allowed around operators and `d`
+/// may be uppercase.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::dice::*;
+/// # use horizon::prng::Yarrow;
+/// let expression = DiceExpr::parse("2d6+3")?;
+/// let mut yarrow_instance = Yarrow::new(42);
+/// println!("{}", expression.roll(&mut yarrow_instance)?);
+/// # Ok::<(), horizon::prngerror::PrngError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiceExpr {
+    terms: Vec<DiceTerm>,
+}
+
+impl DiceExpr {
+    /// Parses a dice expression.
+    ///
+    /// # Parameters
+    ///
+    /// - `notation`: The dice notation to parse.
+    ///
+    /// # Returns
+    ///
+    /// Returns the parsed expression, or `PrngError::InvalidNotation` if `notation` is malformed, has a
+    /// group of zero dice, of more than `MAX_DICE` dice or of zero-sided dice, or overflows.
+    pub fn parse(notation: &str) -> Result<DiceExpr, PrngError> {
+        let mut chars = notation.chars().collect::<Vec<char>>().into_iter().peekable();
+        let mut terms = Vec::new();
+        skip_whitespace(&mut chars);
+        let mut negative = chars.next_if_eq(&'-').is_some();
+
+        loop {
+            skip_whitespace(&mut chars);
+            let count = read_number(&mut chars)?;
+            let atom = if chars.next_if(|c| c.eq_ignore_ascii_case(&'d')).is_some() {
+                let count = count.unwrap_or(1);
+                let sides = read_number(&mut chars)?.ok_or(PrngError::InvalidNotation)?;
+                if count == 0 || count > MAX_DICE || sides == 0 {
+                    return Err(PrngError::InvalidNotation);
+                }
+                Atom::Dice { count, sides }
+            } else {
+                Atom::Constant(count.ok_or(PrngError::InvalidNotation)?)
+            };
+
+            let mut multiplier: i64 = 1;
+            skip_whitespace(&mut chars);
+            while chars.next_if_eq(&'*').is_some() {
+                skip_whitespace(&mut chars);
+                let factor = read_number(&mut chars)?.ok_or(PrngError::InvalidNotation)?;
+                multiplier = multiplier.checked_mul(i64::from(factor)).ok_or(PrngError::InvalidNotation)?;
+                skip_whitespace(&mut chars);
+            }
+            terms.push(DiceTerm { negative, atom, multiplier });
+
+            match chars.next() {
+                None => break,
+                Some('+') => negative = false,
+                Some('-') => negative = true,
+                Some(_) => return Err(PrngError::InvalidNotation),
+            }
+        }
+
+        Ok(DiceExpr { terms })
+    }
+
+    /// Rolls the expression.
+    ///
+    /// Each die is drawn from 8 bytes of `rng` with a multiply-high reduction, whose bias is below
+    /// `sides / 2^64`.
+    ///
+    /// # Parameters
+    ///
+    /// - `rng`: The random source to roll the dice with.
+    ///
+    /// # Returns
+    ///
+    /// Returns the total, `PrngError::InvalidNotation` if it overflows an `i64`, or the error of `rng`.
+    pub fn roll<R: RandomSource>(&self, rng: &mut R) -> Result<i64, PrngError> {
+        let mut total: i64 = 0;
+
+        for term in &self.terms {
+            let value = match term.atom {
+                Atom::Dice { count, sides } => {
+                    let mut sum: i64 = 0;
+                    for _ in 0..count {
+                        sum += roll_die(rng, sides)?;
+                    }
+                    sum
+                }
+                Atom::Constant(constant) => i64::from(constant),
+            };
+            let value = value.checked_mul(term.multiplier).ok_or(PrngError::InvalidNotation)?;
+            total = if term.negative { total.checked_sub(value) } else { total.checked_add(value) }
+                .ok_or(PrngError::InvalidNotation)?;
+        }
+
+        Ok(total)
+    }
+}
+
+/// Parses and rolls a dice expression such as `2d6+3` with any random source.
+///
+/// # Parameters
+///
+/// - `rng`: The random source to roll the dice with.
+/// - `notation`: The dice notation, as accepted by `DiceExpr::parse`.
+///
+/// # Returns
+///
+/// Returns the total, or an error if the notation is invalid or `rng` fails.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::dice::*;
+/// # use horizon::prng::Yarrow;
+/// let mut yarrow_instance = Yarrow::new(42);
+/// let total = roll_notation(&mut yarrow_instance, "1d20-1")?;
+/// println!("{}", total);
+/// # Ok::<(), horizon::prngerror::PrngError>(())
+/// ```
+pub fn roll_notation<R: RandomSource>(rng: &mut R, notation: &str) -> Result<i64, PrngError> {
+    DiceExpr::parse(notation)?.roll(rng)
+}
+
+/// Reads a decimal number, if the next characters are digits.
+///
+/// # Parameters
+///
+/// - `chars`: The remaining characters of the notation.
+///
+/// # Returns
+///
+/// Returns the number, `None` if no digit follows, or `PrngError::InvalidNotation` if it overflows a `u32`.
+fn read_number(chars: &mut Peekable<IntoIter<char>>) -> Result<Option<u32>, PrngError> {
+    let mut number: Option<u32> = None;
+    while let Some(digit) = chars.next_if(|c| c.is_ascii_digit()) {
+        let value = number.unwrap_or(0)
+            .checked_mul(10)
+            .and_then(|value| value.checked_add(digit as u32 - '0' as u32))
+            .ok_or(PrngError::InvalidNotation)?;
+        number = Some(value);
+    }
+    Ok(number)
+}
+
+/// Skips whitespace between tokens.
+///
+/// # Parameters
+///
+/// - `chars`: The remaining characters of the notation.
+fn skip_whitespace(chars: &mut Peekable<IntoIter<char>>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+/// Rolls a single die with `sides` faces.
+///
+/// # Parameters
+///
+/// - `rng`: The random source to draw from.
+/// - `sides`: The number of faces, at least 1.
+///
+/// # Returns
+///
+/// Returns a value in `1..=sides`, or the error of `rng`.
+fn roll_die<R: RandomSource>(rng: &mut R, sides: u32) -> Result<i64, PrngError> {
+    let mut bytes = [0u8; 8];
+    rng.try_fill_bytes(&mut bytes)?;
+    let draw = (u128::from(u64::from_be_bytes(bytes)) * u128::from(sides)) >> 64;
+    Ok(draw as i64 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prng::{ExhaustedPolicy, ReplayRng, Yarrow};
+
+    #[test]
+    fn test_roll_notation_ranges() {
+        let mut rng = Yarrow::new_deterministic(12345);
+        let cases = [
+            ("2d6+3", 5, 15),
+            ("1d20-1", 0, 19),
+            ("4d8*2", 8, 64),
+            ("2d6 + 1d4 - 2", 1, 14),
+            (" 4d8 * 2 ", 8, 64),
+            ("D6", 1, 6),
+            ("-1d4", -4, -1),
+            ("3", 3, 3),
+        ];
+
+        for (notation, min, max) in cases {
+            for _ in 0..50 {
+                let total = rng.roll_notation(notation).unwrap();
+                assert!((min..=max).contains(&total), "{} = {} hors de [{}, {}]", notation, total, min, max);
+            }
+        }
+    }
+
+    #[test]
+    fn test_roll_notation_invalid() {
+        let mut rng = Yarrow::new_deterministic(12345);
+        for notation in ["", "2d", "d0", "0d6", "2x6", "2d6+", "++1", "1d6*", "1001d6", "99999999999", "2d6 3"] {
+            assert_eq!(roll_notation(&mut rng, notation), Err(PrngError::InvalidNotation), "{}", notation);
+        }
+    }
+
+    #[test]
+    fn test_roll_notation_source_error() {
+        let mut rng = ReplayRng::new(vec![0; 8], ExhaustedPolicy::Error);
+        assert_eq!(roll_notation(&mut rng, "1d6"), Ok(1));
+        assert_eq!(roll_notation(&mut rng, "1d6"), Err(PrngError::Exhausted));
+    }
+}
@@ -0,0 +1,444 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use sha3::{Digest, Sha3_256};
+
+use crate::prng::{wipe_bytes, Clock, HorizonRng, RandomSource};
+use crate::prngerror::PrngError;
+
+/// Number of entropy pools of the accumulator.
+const POOLS: usize = 32;
+
+/// Bytes of events pool 0 must have received before the accumulator reseeds the generator.
+const MIN_POOL_SIZE: usize = 64;
+
+/// Maximum number of bytes generated under one key before the generator rekeys.
+const MAX_REQUEST_BYTES: usize = 1 << 20;
+
+/// Size in bytes of the generator key and of each output block.
+const BLOCK_BYTES: usize = 32;
+
+/// Maximum size in bytes of the data of a single random event.
+const MAX_EVENT_BYTES: usize = 32;
+
+/// Domain separation label for the output blocks.
+const BLOCK_LABEL: &[u8] = b"horizon-fortuna-block";
+
+/// The Fortuna cryptographic pseudorandom number generator, Yarrow's successor.
+///
+/// Random events are spread over 32 pools. The generator is reseeded from pool 0 once it holds
+/// enough data, and pool `i` only takes part in every `2^i`-th reseed, so an attacker who can
+/// predict or inject some of the events cannot keep the state compromised for long, whatever their
+/// share: no entropy estimate is needed. Output comes from a generator in counter mode whose key is
+/// replaced after every request, so a later compromise does not reveal earlier output. The block
+/// function is SHA3-256 keyed with the generator key, in place of the block cipher of the original
+/// design.
+///
+/// # Fields
+///
+/// - `key`: The generator key.
+/// - `counter`: The block counter, zero until the generator is first seeded.
+/// - `pools`: The hashers accumulating the events of each pool.
+/// - `pool0_len`: The number of event bytes added to pool 0 since the last reseed.
+/// - `reseed_count`: The number of reseeds from the pools, which decides which pools the next one uses.
+/// - `last_reseed`: The clock time of the last reseed from the pools, if any.
+/// - `next_pool`: The pool each event source adds its next event to.
+/// - `clock`: When set, the clock limiting reseeds from the pools to one per second.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::fortuna::*;
+/// # use horizon::prngerror::PrngError;
+/// let mut fortuna = Fortuna::new();
+/// fortuna.reseed(b"32 bytes read from a seed file...");
+/// let key = fortuna.try_random_data(32)?;
+/// # Ok::<(), PrngError>(())
+/// ```
+pub struct Fortuna {
+    key: [u8; BLOCK_BYTES],
+    counter: u128,
+    pools: Vec<Sha3_256>,
+    pool0_len: usize,
+    reseed_count: u64,
+    last_reseed: Option<u64>,
+    next_pool: BTreeMap<u8, usize>,
+    clock: Option<Box<dyn Clock + Send>>,
+}
+
+impl Fortuna {
+    /// Creates a new, unseeded `Fortuna`.
+    ///
+    /// Without a clock, the generator reseeds from the pools whenever pool 0 holds enough data; use
+    /// `with_clock` to limit reseeds to one per second.
+    ///
+    /// # Returns
+    ///
+    /// Returns a generator that refuses to produce output until it is seeded, either with `reseed`
+    /// or from the events added with `add_random_event`.
+    pub fn new() -> Self {
+        Fortuna {
+            key: [0; BLOCK_BYTES],
+            counter: 0,
+            pools: vec![Sha3_256::new(); POOLS],
+            pool0_len: 0,
+            reseed_count: 0,
+            last_reseed: None,
+            next_pool: BTreeMap::new(),
+            clock: None,
+        }
+    }
+
+    /// Creates a new, unseeded `Fortuna` that reseeds from the pools at most once per second of
+    /// `clock`.
+    ///
+    /// The limit keeps an attacker who can trigger reseeds from emptying the pools before they have
+    /// accumulated enough entropy.
+    ///
+    /// # Parameters
+    ///
+    /// - `clock`: The clock read before each reseed from the pools.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new, unseeded generator.
+    pub fn with_clock(clock: Box<dyn Clock + Send>) -> Self {
+        let mut fortuna = Fortuna::new();
+        fortuna.clock = Some(clock);
+        fortuna
+    }
+
+    /// Adds a random event from an entropy source to the pools.
+    ///
+    /// Each source spreads its events over the 32 pools in turn, so the pools fill evenly whatever
+    /// the number of sources. The event is hashed into its pool together with the source ID and its
+    /// length.
+    ///
+    /// # Parameters
+    ///
+    /// - `source`: The ID of the entropy source, chosen by the caller.
+    /// - `data`: The event data, e.g. a timestamp or an interrupt counter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` is empty or longer than 32 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::fortuna::*;
+    /// let mut fortuna = Fortuna::new();
+    /// // Pool 0 gets every 32nd event of the source and must receive 64 bytes.
+    /// for sample in 0u8..64 {
+    ///     fortuna.add_random_event(0, &[sample; 32]);
+    /// }
+    /// assert!(fortuna.try_random_data(16).is_ok());
+    /// ```
+    pub fn add_random_event(&mut self, source: u8, data: &[u8]) {
+        assert!(
+            (1..=MAX_EVENT_BYTES).contains(&data.len()),
+            "Event data must be 1 to {} bytes long, got {}",
+            MAX_EVENT_BYTES,
+            data.len()
+        );
+
+        let pool = self.next_pool.entry(source).or_insert(0);
+        let index = *pool;
+        *pool = (index + 1) % POOLS;
+
+        self.pools[index].update([source, data.len() as u8]);
+        self.pools[index].update(data);
+        if index == 0 {
+            self.pool0_len += 2 + data.len();
+        }
+    }
+
+    /// Reseeds the generator directly, e.g. from a seed file saved by a previous run.
+    ///
+    /// The new key is the SHA3-256 hash of the current key and `seed`, so previous seeds keep
+    /// contributing. The first call makes the generator seeded.
+    ///
+    /// # Parameters
+    ///
+    /// - `seed`: The seed material.
+    pub fn reseed(&mut self, seed: &[u8]) {
+        let mut hasher = Sha3_256::new();
+        hasher.update(self.key);
+        hasher.update(seed);
+        wipe_bytes(&mut self.key);
+        self.key.copy_from_slice(&hasher.finalize());
+        self.counter = self.counter.wrapping_add(1);
+    }
+
+    /// Returns whether the generator has been seeded and can produce output.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` once `reseed` was called or the pools reseeded the generator.
+    pub fn is_seeded(&self) -> bool {
+        self.counter != 0
+    }
+
+    /// Returns the number of reseeds from the pools performed so far.
+    ///
+    /// # Returns
+    ///
+    /// Returns the reseed count.
+    pub fn reseed_count(&self) -> u64 {
+        self.reseed_count
+    }
+
+    /// Generates random bytes, reseeding from the pools first when they are due.
+    ///
+    /// # Parameters
+    ///
+    /// - `count`: The number of random bytes to generate.
+    ///
+    /// # Returns
+    ///
+    /// Returns the generated bytes, or `PrngError::NotSeeded` if the generator was never seeded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::fortuna::*;
+    /// # use horizon::prngerror::PrngError;
+    /// let mut fortuna = Fortuna::new();
+    /// assert_eq!(fortuna.try_random_data(16), Err(PrngError::NotSeeded));
+    /// fortuna.reseed(b"seed");
+    /// assert_eq!(fortuna.try_random_data(16)?.len(), 16);
+    /// # Ok::<(), PrngError>(())
+    /// ```
+    pub fn try_random_data(&mut self, count: usize) -> Result<Vec<u8>, PrngError> {
+        let mut bytes = vec![0u8; count];
+        self.try_fill(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Fills a caller-provided buffer with random bytes, reseeding from the pools first when they
+    /// are due.
+    ///
+    /// The key is replaced after the buffer is filled, and after every 1 MiB within a larger buffer.
+    ///
+    /// # Parameters
+    ///
+    /// - `dest`: The buffer to fill.
+    ///
+    /// # Returns
+    ///
+    /// Returns `PrngError::NotSeeded` if the generator was never seeded, leaving `dest` untouched.
+    pub fn try_fill(&mut self, dest: &mut [u8]) -> Result<(), PrngError> {
+        self.reseed_from_pools_if_due();
+        if !self.is_seeded() {
+            return Err(PrngError::NotSeeded);
+        }
+
+        for request in dest.chunks_mut(MAX_REQUEST_BYTES) {
+            self.generate_blocks(request);
+            let mut new_key = [0u8; BLOCK_BYTES];
+            self.generate_blocks(&mut new_key);
+            wipe_bytes(&mut self.key);
+            self.key = new_key;
+            wipe_bytes(&mut new_key);
+        }
+        Ok(())
+    }
+
+    /// Reseeds the generator from the pools if pool 0 holds enough data and, with a clock, no reseed
+    /// from the pools happened in the current second.
+    ///
+    /// Pool `i` is used if `2^i` divides the new reseed count; the pools used are emptied.
+    fn reseed_from_pools_if_due(&mut self) {
+        if self.pool0_len < MIN_POOL_SIZE {
+            return;
+        }
+        let now = self.clock.as_ref().map(|clock| clock.now_secs());
+        if let (Some(now), Some(last)) = (now, self.last_reseed) {
+            if now <= last {
+                return;
+            }
+        }
+
+        self.reseed_count += 1;
+        let mut seed = Vec::with_capacity(pools_due(self.reseed_count) * BLOCK_BYTES);
+        for pool in &mut self.pools[..pools_due(self.reseed_count)] {
+            seed.extend_from_slice(&core::mem::take(pool).finalize());
+        }
+        self.pool0_len = 0;
+        self.last_reseed = now;
+        self.reseed(&seed);
+        wipe_bytes(&mut seed);
+    }
+
+    /// Fills `dest` with output blocks, each the SHA3-256 hash of a label, the key and the counter,
+    /// incrementing the counter after each block.
+    ///
+    /// # Parameters
+    ///
+    /// - `dest`: The buffer to fill; a partial last block is truncated.
+    fn generate_blocks(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(BLOCK_BYTES) {
+            let mut hasher = Sha3_256::new();
+            hasher.update(BLOCK_LABEL);
+            hasher.update(self.key);
+            hasher.update(self.counter.to_le_bytes());
+            chunk.copy_from_slice(&hasher.finalize()[..chunk.len()]);
+            self.counter = self.counter.wrapping_add(1);
+        }
+    }
+}
+
+/// Returns the number of pools used by the reseed with the given count: pool `i` is used if `2^i`
+/// divides `reseed_count`.
+///
+/// # Parameters
+///
+/// - `reseed_count`: The count of the reseed, starting at 1.
+///
+/// # Returns
+///
+/// Returns the number of leading pools to drain, between 1 and `POOLS`.
+fn pools_due(reseed_count: u64) -> usize {
+    (reseed_count.trailing_zeros() as usize + 1).min(POOLS)
+}
+
+impl Default for Fortuna {
+    fn default() -> Self {
+        Fortuna::new()
+    }
+}
+
+/// Formats the generator's public metadata only; the key and the pools are never shown.
+impl fmt::Debug for Fortuna {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Fortuna")
+            .field("seeded", &self.is_seeded())
+            .field("reseed_count", &self.reseed_count)
+            .field("pool0_len", &self.pool0_len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for Fortuna {
+    fn drop(&mut self) {
+        wipe_bytes(&mut self.key);
+    }
+}
+
+impl RandomSource for Fortuna {
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), PrngError> {
+        self.try_fill(dest)
+    }
+}
+
+impl HorizonRng for Fortuna {
+    fn reseed(&mut self, seed: &[u8]) {
+        Fortuna::reseed(self, seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Clone)]
+    struct MockClock {
+        secs: Arc<AtomicU64>,
+    }
+
+    impl Clock for MockClock {
+        fn now_secs(&self) -> u64 {
+            self.secs.load(Ordering::SeqCst)
+        }
+    }
+
+    fn fill_pool0(fortuna: &mut Fortuna) {
+        // Source 0 adds 32 events, one per pool, so 2 rounds put 2 x (2 + 32) bytes in pool 0.
+        for round in 0..2u8 {
+            for pool in 0..POOLS as u8 {
+                fortuna.add_random_event(0, &[round ^ pool; 32]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unseeded() {
+        let mut fortuna = Fortuna::new();
+        assert!(!fortuna.is_seeded());
+        assert_eq!(fortuna.try_random_data(16), Err(PrngError::NotSeeded), "Un générateur non initialisé ne doit rien produire");
+        fortuna.add_random_event(0, &[1; 32]);
+        assert_eq!(fortuna.try_random_data(16), Err(PrngError::NotSeeded), "Le pool 0 n'est pas encore assez rempli");
+    }
+
+    #[test]
+    fn test_reseed_and_rekey() {
+        let mut a = Fortuna::new();
+        let mut b = Fortuna::new();
+        a.reseed(b"seed");
+        b.reseed(b"seed");
+        let first = a.try_random_data(100).unwrap();
+        assert_eq!(first, b.try_random_data(100).unwrap(), "La même graine doit donner la même sortie");
+        assert_ne!(first, a.try_random_data(100).unwrap(), "La clé doit changer après chaque requête");
+        assert_ne!(a.key, [0; BLOCK_BYTES]);
+
+        let mut other = Fortuna::new();
+        other.reseed(b"other seed");
+        assert_ne!(other.try_random_data(100).unwrap(), first);
+    }
+
+    #[test]
+    fn test_reseed_from_pools() {
+        let mut fortuna = Fortuna::new();
+        fill_pool0(&mut fortuna);
+        assert_eq!(fortuna.reseed_count(), 0);
+        assert!(fortuna.try_random_data(16).is_ok(), "Les événements doivent initialiser le générateur");
+        assert_eq!(fortuna.reseed_count(), 1);
+        assert_eq!(fortuna.pool0_len, 0);
+
+        fortuna.try_random_data(16).unwrap();
+        assert_eq!(fortuna.reseed_count(), 1, "Pas de réensemencement sans nouveaux événements");
+        fill_pool0(&mut fortuna);
+        fortuna.try_random_data(16).unwrap();
+        assert_eq!(fortuna.reseed_count(), 2);
+
+        assert_eq!([pools_due(1), pools_due(2), pools_due(3), pools_due(4), pools_due(8)], [1, 2, 1, 3, 4]);
+        assert_eq!(pools_due(1 << 40), POOLS);
+    }
+
+    #[test]
+    fn test_reseed_rate_limit() {
+        let secs = Arc::new(AtomicU64::new(100));
+        let mut fortuna = Fortuna::with_clock(Box::new(MockClock { secs: Arc::clone(&secs) }));
+        fill_pool0(&mut fortuna);
+        fortuna.try_random_data(16).unwrap();
+        fill_pool0(&mut fortuna);
+        fortuna.try_random_data(16).unwrap();
+        assert_eq!(fortuna.reseed_count(), 1, "Un seul réensemencement par seconde");
+
+        secs.store(101, Ordering::SeqCst);
+        fortuna.try_random_data(16).unwrap();
+        assert_eq!(fortuna.reseed_count(), 2);
+    }
+
+    #[test]
+    fn test_random_source() {
+        fn draw<R: RandomSource>(rng: &mut R) -> u64 {
+            rng.next_u64()
+        }
+
+        let mut fortuna = Fortuna::new();
+        assert_eq!(fortuna.try_fill_bytes(&mut [0u8; 8]), Err(PrngError::NotSeeded));
+        fortuna.reseed(b"seed");
+        assert_ne!(draw(&mut fortuna), draw(&mut fortuna));
+    }
+
+    #[test]
+    #[should_panic(expected = "Event data must be 1 to 32 bytes long")]
+    fn test_add_random_event_too_long() {
+        Fortuna::new().add_random_event(0, &[0; 33]);
+    }
+}
@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use sha3::{Sha3_512, Digest};
 /// Computes the Hash-based Message Authentication Code (HMAC) using the SHA3-512 hashing algorithm.
 ///
@@ -12,13 +13,13 @@ use sha3::{Sha3_512, Digest};
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```rust,ignore
 /// let key = vec![/* vector of u8 representing key */];
 /// let message = vec![/* vector of u8 representing message */];
 /// let hmac_result = hmac(&key, &message);
 /// println!("{:?}", hmac_result);
 /// ```
-fn hmac(key: &[u8], message: &[u8]) -> Vec<u8> {
+pub(crate) fn hmac(key: &[u8], message: &[u8]) -> Vec<u8> {
     const BLOCK_SIZE: usize = 128;
 
 
@@ -61,19 +62,20 @@ fn hmac(key: &[u8], message: &[u8]) -> Vec<u8> {
 /// # Examples
 ///
 /// ```rust
+/// # use horizon::kdfwagen::*;
 /// let password = vec![/* vector of u8 representing password */];
 /// let salt = vec![/* vector of u8 representing salt */];
 /// let iterations = 1000;
 /// let derived_key = kdfwagen(&password, &salt, iterations);
 /// println!("{:?}", derived_key);
 /// ```
-pub(crate) fn kdfwagen(password: &[u8], salt: &[u8], iterations: usize) -> Vec<u8> {
+pub fn kdfwagen(password: &[u8], salt: &[u8], iterations: usize) -> Vec<u8> {
     const PRF_OUTPUT_SIZE: usize = 64;
     const KEY_LENGTH: usize = 512;
 
 
     let mut result = Vec::new();
-    let mut block_count = (KEY_LENGTH + PRF_OUTPUT_SIZE - 1) / PRF_OUTPUT_SIZE;
+    let mut block_count = KEY_LENGTH.div_ceil(PRF_OUTPUT_SIZE);
 
     if block_count > 255 {
         block_count = 255;
@@ -91,7 +93,7 @@ pub(crate) fn kdfwagen(password: &[u8], salt: &[u8], iterations: usize) -> Vec<u
             xor_result.iter_mut().zip(u.iter()).for_each(|(a, b)| *a ^= b);
         }
 
-        result.extend_from_slice(&xor_result[..std::cmp::min(PRF_OUTPUT_SIZE, KEY_LENGTH)]);
+        result.extend_from_slice(&xor_result[..core::cmp::min(PRF_OUTPUT_SIZE, KEY_LENGTH)]);
     }
 
     result.resize(KEY_LENGTH, 0);
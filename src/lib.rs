@@ -0,0 +1,50 @@
+//! Horizon's cryptographic building blocks, usable from other crates.
+//!
+//! The main entry point is the [`prng::Yarrow`] generator, re-exported at the crate root together
+//! with [`prngerror::PrngError`]. Its supported core API is:
+//!
+//! - [`prng::Yarrow::new`] to create a generator from a seed;
+//! - [`prng::Yarrow::generate_random_bytes`] to generate random bytes;
+//! - [`prng::Yarrow::generate_random_number`] to generate a random `u64`;
+//! - [`prng::Yarrow::generate_bounded_number`] to generate a random `u64` within a range.
+//!
+//! ```rust
+//! use horizon::Yarrow;
+//!
+//! let mut yarrow_instance = Yarrow::new(42);
+//! let random_bytes = yarrow_instance.generate_random_bytes(16);
+//! let dice_roll = yarrow_instance.generate_bounded_number(1, 6);
+//! println!("{:?} {}", random_bytes, dice_roll);
+//! ```
+//!
+//! For one-off values in scripts, `horizon::random::<T>()` draws from a thread-local generator seeded
+//! from OS entropy, and `Yarrow::default()` creates such a generator.
+//!
+//! The `std` feature is enabled by default. Without it the crate is `no_std` and only needs
+//! `alloc`: the generator and every module built on it remain available, while the parts that need
+//! the operating system (OS entropy, threads, locks and hash maps) are left out. So are the float
+//! samplers built on logarithms, exponentials or trigonometry (normal, Laplace, log-uniform and
+//! Benford draws, `generate_f64_full`, palettes and the Shannon estimate), which `core` does not
+//! provide. There is no wall clock either, so the reseed window runs on the time set with
+//! `Yarrow::set_time_counter`.
+//!
+//! The `horizon` binary needs the `cli` feature, also on by default, which pulls in its own
+//! dependencies. Library users can opt out with `default-features = false, features = ["std"]`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod coinflip;
+pub mod dice;
+pub mod fortuna;
+pub mod kdfwagen;
+pub mod pipeline;
+pub mod prng;
+pub mod prngerror;
+pub mod vrf;
+
+pub use prng::Yarrow;
+pub use prngerror::PrngError;
+
+#[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+pub use prng::random;
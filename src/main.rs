@@ -1,7 +1,6 @@
 mod systemtrayerror;
-mod kdfwagen;
+#[allow(dead_code)]
 mod cryptex;
-mod prng;
 
 use std::collections::hash_map::DefaultHasher;
 use rand::Rng;
@@ -12,7 +11,7 @@ use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 use rand::rngs::OsRng;
 use rayon::prelude::*;
-use crate::kdfwagen::kdfwagen;
+use horizon::kdfwagen::kdfwagen;
 use crate::systemtrayerror::SystemTrayError;
 
 //v 0.5.12
@@ -236,7 +235,7 @@ fn generate_key2(seed: &str) -> Result<String, SystemTrayError> {
         return Err(SystemTrayError::new(4));
     }
 
-    let seed = kdfwagen::kdfwagen(seed.as_bytes(), get_salt().as_bytes(), 30); //change salt by unique pc id
+    let seed = kdfwagen(seed.as_bytes(), get_salt().as_bytes(), 30); //change salt by unique pc id
 
     Ok(hex::encode(seed))
 }
@@ -493,8 +492,7 @@ fn localization() -> &'static str {
 pub fn shift_bits(cipher_text: Vec<u8>, key: &[u8]) -> Vec<u8> {
     cipher_text.par_iter().enumerate().map(|(i, &byte)| {
         let shift_amount = key[i % key.len()];
-        let rotated_byte = byte.rotate_left(shift_amount as u32);
-        rotated_byte
+        byte.rotate_left(shift_amount as u32)
     }).collect::<Vec<u8>>() // Collect into a Vec<u8>
 }
 
@@ -525,8 +523,7 @@ pub fn shift_bits(cipher_text: Vec<u8>, key: &[u8]) -> Vec<u8> {
 pub fn unshift_bits(cipher_text: Vec<u8>, key: &[u8]) -> Vec<u8> {
     cipher_text.par_iter().enumerate().map(|(i, &byte)| {
         let shift_amount = key[i % key.len()];
-        let rotated_byte = byte.rotate_right(shift_amount as u32);
-        rotated_byte
+        byte.rotate_right(shift_amount as u32)
     }).collect::<Vec<u8>>() // Collect into a Vec<u8>
 }
 
@@ -649,7 +646,7 @@ mod tests {
         let characters = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789!@#$%^&*()_+-={}[]<>?/|.,:;\"'`~ ";
         let seed = 1234567890;
 
-        let actual_table = table2(&characters, seed);
+        let actual_table = table2(characters, seed);
 
         // Vérifiez ici les propriétés spécifiques de votre table.
         // Par exemple, vous pouvez vérifier que la taille de la table est correcte.
@@ -0,0 +1,222 @@
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+use crate::kdfwagen::hmac;
+use crate::prng::RandomSource;
+
+/// The alphabet used by the `Base64` transform (standard, with `=` padding).
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// A step of an `OutputPipeline`, turning bytes into other bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::pipeline::*;
+/// # use horizon::prng::Yarrow;
+/// struct Invert;
+///
+/// impl Transform for Invert {
+///     fn apply(&self, input: Vec<u8>) -> Vec<u8> {
+///         input.into_iter().map(|byte| !byte).collect()
+///     }
+/// }
+/// ```
+pub trait Transform {
+    /// Returns the transformed bytes.
+    fn apply(&self, input: Vec<u8>) -> Vec<u8>;
+}
+
+/// Leaves the bytes unchanged.
+pub struct Identity;
+
+impl Transform for Identity {
+    fn apply(&self, input: Vec<u8>) -> Vec<u8> {
+        input
+    }
+}
+
+/// Whitens the bytes with HMAC-SHA3-512, keeping their length.
+///
+/// Each 64-byte chunk is replaced by the HMAC of its index and contents under `key`.
+pub struct HmacWhiten {
+    key: Vec<u8>,
+}
+
+impl Transform for HmacWhiten {
+    fn apply(&self, input: Vec<u8>) -> Vec<u8> {
+        input.chunks(64)
+            .enumerate()
+            .flat_map(|(index, chunk)| {
+                let mut message = (index as u64).to_be_bytes().to_vec();
+                message.extend_from_slice(chunk);
+                hmac(&self.key, &message).into_iter().take(chunk.len())
+            })
+            .collect()
+    }
+}
+
+/// XORs the bytes with a repeating key.
+pub struct XorKey {
+    key: Vec<u8>,
+}
+
+impl Transform for XorKey {
+    fn apply(&self, input: Vec<u8>) -> Vec<u8> {
+        if self.key.is_empty() {
+            return input;
+        }
+        input.iter().zip(self.key.iter().cycle()).map(|(byte, key)| byte ^ key).collect()
+    }
+}
+
+/// Encodes the bytes as lowercase hexadecimal text.
+pub struct Hex;
+
+impl Transform for Hex {
+    fn apply(&self, input: Vec<u8>) -> Vec<u8> {
+        hex::encode(input).into_bytes()
+    }
+}
+
+/// Encodes the bytes as standard, padded Base64 text.
+pub struct Base64;
+
+impl Transform for Base64 {
+    fn apply(&self, input: Vec<u8>) -> Vec<u8> {
+        let mut output = Vec::with_capacity(input.len().div_ceil(3) * 4);
+        for chunk in input.chunks(3) {
+            let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+            let group = u32::from(bytes[0]) << 16 | u32::from(bytes[1]) << 8 | u32::from(bytes[2]);
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    output.push(BASE64_ALPHABET[(group >> (18 - 6 * i) & 0x3F) as usize]);
+                } else {
+                    output.push(b'=');
+                }
+            }
+        }
+        output
+    }
+}
+
+/// A sequence of `Transform`s applied to raw generator bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::pipeline::*;
+/// # use horizon::prng::Yarrow;
+/// let mut yarrow_instance = Yarrow::new(42);
+/// let token = OutputPipeline::new().hmac_whiten(b"key").hex().gen_string(&mut yarrow_instance, 16);
+/// println!("{}", token);
+/// ```
+#[derive(Default)]
+pub struct OutputPipeline {
+    transforms: Vec<Box<dyn Transform>>,
+}
+
+impl OutputPipeline {
+    /// Creates an empty pipeline, which returns raw generator bytes.
+    pub fn new() -> Self {
+        OutputPipeline { transforms: Vec::new() }
+    }
+
+    /// Appends a transform to the pipeline.
+    ///
+    /// # Parameters
+    ///
+    /// - `transform`: The transform applied after the ones already in the pipeline.
+    ///
+    /// # Returns
+    ///
+    /// Returns the extended pipeline.
+    pub fn then(mut self, transform: impl Transform + 'static) -> Self {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    /// Appends an `Identity` transform.
+    pub fn identity(self) -> Self {
+        self.then(Identity)
+    }
+
+    /// Appends an `HmacWhiten` transform keyed with `key`.
+    pub fn hmac_whiten(self, key: &[u8]) -> Self {
+        self.then(HmacWhiten { key: key.to_vec() })
+    }
+
+    /// Appends an `XorKey` transform with the repeating `key`.
+    pub fn xor_key(self, key: &[u8]) -> Self {
+        self.then(XorKey { key: key.to_vec() })
+    }
+
+    /// Appends a `Hex` transform.
+    pub fn hex(self) -> Self {
+        self.then(Hex)
+    }
+
+    /// Appends a `Base64` transform.
+    pub fn base64(self) -> Self {
+        self.then(Base64)
+    }
+
+    /// Draws `count` raw bytes from `rng` and runs them through the pipeline.
+    ///
+    /// # Parameters
+    ///
+    /// - `rng`: The random source to draw from.
+    /// - `count`: The number of raw bytes to draw.
+    ///
+    /// # Returns
+    ///
+    /// Returns the transformed bytes.
+    pub fn gen<R: RandomSource>(&self, rng: &mut R, count: usize) -> Vec<u8> {
+        let mut raw = vec![0u8; count];
+        rng.fill_bytes(&mut raw);
+        self.transforms.iter().fold(raw, |bytes, transform| transform.apply(bytes))
+    }
+
+    /// Draws `count` raw bytes from `rng` and runs them through the pipeline, as text.
+    ///
+    /// Intended for pipelines ending with a text encoding such as `hex` or `base64`; invalid UTF-8
+    /// sequences are replaced.
+    ///
+    /// # Parameters
+    ///
+    /// - `rng`: The random source to draw from.
+    /// - `count`: The number of raw bytes to draw.
+    ///
+    /// # Returns
+    ///
+    /// Returns the transformed bytes as a `String`.
+    pub fn gen_string<R: RandomSource>(&self, rng: &mut R, count: usize) -> String {
+        String::from_utf8_lossy(&self.gen(rng, count)).into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prng::Yarrow;
+
+    #[test]
+    fn test_whiten_hex_pipeline() {
+        let pipeline = OutputPipeline::new().hmac_whiten(b"key").hex();
+        let output = pipeline.gen_string(&mut Yarrow::new_deterministic(12345), 100);
+
+        assert_eq!(output.len(), 200);
+        assert!(output.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(output, pipeline.gen_string(&mut Yarrow::new_deterministic(12345), 100));
+        assert_ne!(output, OutputPipeline::new().hex().gen_string(&mut Yarrow::new_deterministic(12345), 100));
+    }
+
+    #[test]
+    fn test_builtin_transforms() {
+        assert_eq!(Identity.apply(vec![1, 2, 3]), vec![1, 2, 3]);
+        assert_eq!(XorKey { key: vec![0xFF] }.apply(vec![0x0F, 0xF0]), vec![0xF0, 0x0F]);
+        assert_eq!(Base64.apply(b"Man".to_vec()), b"TWFu");
+        assert_eq!(Base64.apply(b"Ma".to_vec()), b"TWE=");
+        assert_eq!(Base64.apply(b"M".to_vec()), b"TQ==");
+        assert_eq!(HmacWhiten { key: b"key".to_vec() }.apply(vec![0; 100]).len(), 100);
+    }
+}
@@ -1,282 +1,7855 @@
-use std::collections::VecDeque;
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::any::TypeId;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Bound, RangeBounds};
+use core::ptr;
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::AtomicU64;
+use core::sync::atomic::{self, AtomicUsize, Ordering};
+#[cfg(feature = "std")]
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::collections::hash_map::DefaultHasher;
+#[cfg(feature = "std")]
+use std::hash::{Hash, Hasher};
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+#[cfg(feature = "std")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "std")]
+use std::time::Instant;
+#[cfg(all(feature = "std", not(feature = "deterministic-only")))]
 use std::time::{SystemTime, UNIX_EPOCH};
 use sha3::{Sha3_512, Digest};
+use crate::dice;
+use crate::prngerror::PrngError;
 
-/// Represents the Yarrow cryptographic pseudorandom number generator.
+/// Mixes new input into the Yarrow entropy pool.
 ///
-/// # Fields
+/// Implementations receive the current pool and the input to fold into it, and return the
+/// next pool state. This lets alternative mixing functions be plugged into `Yarrow` without
+/// changing the generator itself.
 ///
-/// - `seed`: A 64-bit unsigned integer representing the initial seed for the generator.
-/// - `pool`: A deque of unsigned 8-bit integers serving as the entropy pool.
-/// - `last_reseed_time`: A 64-bit unsigned integer representing the time of the last reseed operation.
+/// The mixer only drives the pool updates and the output blocks. The Fortuna accumulators, the fast
+/// and slow pools, `reseed_bytes`, rekeying and the child states derived by `split` and
+/// `fork_stream` always hash with SHA3-512, and the entropy estimate stays capped at 512 bits
+/// whatever the mixer's output length. Snapshots do not record the mixer either, so `from_snapshot`
+/// rejects the snapshot of a generator whose mixer does not mix like `Sha3Mixer`.
 ///
 /// # Examples
 ///
 /// ```rust
-/// let yarrow_instance = Yarrow {
-///     seed: 42,
-///     pool: VecDeque::new(),
-///     last_reseed_time: 0,
-/// };
+/// # use horizon::prng::{Mixer, Yarrow};
+/// #[derive(Clone)]
+/// struct XorMixer;
+///
+/// impl Mixer for XorMixer {
+///     fn mix(&mut self, pool: &[u8], input: &[u8]) -> Vec<u8> {
+///         let mut mixed = [0u8; 64];
+///         for (i, byte) in pool.iter().chain(input).enumerate() {
+///             mixed[i % 64] ^= byte.rotate_left(i as u32 % 8);
+///         }
+///         mixed.to_vec()
+///     }
+/// }
+///
+/// let mut yarrow_instance = Yarrow::with_mixer(42, Box::new(XorMixer));
+/// let random_bytes = yarrow_instance.generate_random_bytes(16);
 /// ```
-struct Yarrow {
-    seed: u64,
-    pool: VecDeque<u8>,
-    last_reseed_time: u64,
+pub trait Mixer: MixerClone {
+    /// Returns the new pool state obtained by mixing `input` into `pool`.
+    fn mix(&mut self, pool: &[u8], input: &[u8]) -> Vec<u8>;
+
+    /// Returns `true` if the mixer mixes exactly like `Sha3Mixer`, the mixer a restored snapshot
+    /// uses. Defaults to `false`.
+    fn is_sha3_512(&self) -> bool {
+        false
+    }
 }
 
-/// Implements methods for the Yarrow cryptographic pseudorandom number generator.
-impl Yarrow {
-    /// Creates a new instance of `Yarrow` with the specified seed.
-    ///
-    /// # Parameters
-    ///
-    /// - `seed`: A 64-bit unsigned integer serving as the initial seed for the generator.
+/// Clones a boxed `Mixer`; implemented for every `Mixer` that is `Clone`.
+pub trait MixerClone {
+    /// Returns a boxed copy of the mixer.
+    fn clone_box(&self) -> Box<dyn Mixer + Send>;
+}
+
+impl<T: Mixer + Clone + Send + 'static> MixerClone for T {
+    fn clone_box(&self) -> Box<dyn Mixer + Send> {
+        Box::new(self.clone())
+    }
+}
+
+/// The default mixer, hashing the pool followed by the input with SHA3-512.
+#[derive(Clone)]
+pub struct Sha3Mixer;
+
+impl Mixer for Sha3Mixer {
+    fn mix(&mut self, pool: &[u8], input: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha3_512::new();
+        hasher.update(pool);
+        hasher.update(input);
+        hasher.finalize().to_vec()
+    }
+
+    fn is_sha3_512(&self) -> bool {
+        true
+    }
+}
+
+/// A mixer hashing the pool followed by the input with any `Digest`, e.g. `sha3::Sha3_256`.
+///
+/// The pool is replaced by the digest of each mix, so its size follows the digest's output length:
+/// 32 bytes with SHA3-256, 64 bytes with SHA3-512. `DigestMixer<Sha3_512>` mixes exactly like
+/// `Sha3Mixer`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::prng::{DigestMixer, Yarrow};
+/// let yarrow_instance = Yarrow::with_mixer(42, Box::new(DigestMixer::<sha3::Sha3_256>::new()));
+/// ```
+pub struct DigestMixer<D> {
+    digest: PhantomData<fn() -> D>,
+}
+
+impl<D> DigestMixer<D> {
+    /// Creates a mixer hashing with `D`.
+    pub fn new() -> Self {
+        DigestMixer { digest: PhantomData }
+    }
+}
+
+impl<D> Default for DigestMixer<D> {
+    fn default() -> Self {
+        DigestMixer::new()
+    }
+}
+
+impl<D> Clone for DigestMixer<D> {
+    fn clone(&self) -> Self {
+        DigestMixer::new()
+    }
+}
+
+impl<D: Digest + 'static> Mixer for DigestMixer<D> {
+    fn mix(&mut self, pool: &[u8], input: &[u8]) -> Vec<u8> {
+        let mut hasher = D::new();
+        hasher.update(pool);
+        hasher.update(input);
+        hasher.finalize().to_vec()
+    }
+
+    fn is_sha3_512(&self) -> bool {
+        TypeId::of::<D>() == TypeId::of::<Sha3_512>()
+    }
+}
+
+/// Tells a `Yarrow` generator the current time, which drives its 60-second reseed window.
+///
+/// The system clock is used by default; a custom clock lets tests and embedded targets control
+/// when the reseed window elapses.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::prng::{Clock, YarrowBuilder};
+/// #[derive(Clone)]
+/// struct FixedClock(u64);
+///
+/// impl Clock for FixedClock {
+///     fn now_secs(&self) -> u64 {
+///         self.0
+///     }
+/// }
+///
+/// let yarrow_instance = YarrowBuilder::new(42).clock(Box::new(FixedClock(3600))).build();
+/// ```
+pub trait Clock: ClockClone {
+    /// Returns the current time in seconds.
+    fn now_secs(&self) -> u64;
+}
+
+/// Clones a boxed `Clock`; implemented for every `Clock` that is `Clone`.
+pub trait ClockClone {
+    /// Returns a boxed copy of the clock.
+    fn clone_box(&self) -> Box<dyn Clock + Send>;
+}
+
+impl<T: Clock + Clone + Send + 'static> ClockClone for T {
+    fn clone_box(&self) -> Box<dyn Clock + Send> {
+        Box::new(self.clone())
+    }
+}
+
+/// The default clock, reading the number of seconds since the Unix epoch from the system clock.
+///
+/// A system clock set before the Unix epoch reads as zero instead of panicking: the reseed window
+/// then treats it as a clock that went backwards, and skips the seed update until it recovers.
+#[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+#[derive(Clone)]
+pub struct SystemClock;
+
+#[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+impl SystemClock {
+    /// Reads the number of seconds since the Unix epoch from the system clock.
     ///
     /// # Returns
     ///
-    /// Returns a new `Yarrow` instance with the given seed.
+    /// Returns the number of seconds, or `PrngError::ClockError` if the system clock is set before
+    /// the Unix epoch.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// let yarrow_instance = Yarrow::new(42);
+    /// # use horizon::prng::SystemClock;
+    /// let now = SystemClock.try_now_secs()?;
+    /// # Ok::<(), horizon::prngerror::PrngError>(())
     /// ```
-    fn new(seed: u64) -> Self {
-        Yarrow {
-            seed,
-            pool: VecDeque::new(),
-            last_reseed_time: 0,
+    pub fn try_now_secs(&self) -> Result<u64, PrngError> {
+        secs_since_epoch(SystemTime::now())
+    }
+}
+
+#[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+impl Clock for SystemClock {
+    fn now_secs(&self) -> u64 {
+        self.try_now_secs().unwrap_or(0)
+    }
+}
+
+/// Converts a system time into a number of seconds since the Unix epoch.
+///
+/// # Parameters
+///
+/// - `time`: The time to convert.
+///
+/// # Returns
+///
+/// Returns the number of seconds, or `PrngError::ClockError` if `time` is before the Unix epoch.
+#[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+fn secs_since_epoch(time: SystemTime) -> Result<u64, PrngError> {
+    time.duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .map_err(|_| PrngError::ClockError)
+}
+
+/// A source of random bytes that generic code can draw from.
+///
+/// Implemented by `Yarrow` as well as by helpers such as `ReplayRng`, so consumers can be written
+/// once and fed either real or recorded randomness; `ReplayRng::from_u64s` scripts the values
+/// returned by `next_u64` in tests.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::prng::RandomSource;
+/// fn roll<R: RandomSource>(rng: &mut R) -> u64 {
+///     rng.next_u64() % 6 + 1
+/// }
+/// ```
+pub trait RandomSource {
+    /// Fills `dest` with random bytes, or returns an error if the source cannot serve them.
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), PrngError>;
+
+    /// Fills `dest` with random bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source cannot serve the requested bytes.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(err) = self.try_fill_bytes(dest) {
+            panic!("{}", err);
         }
     }
 
-    /// Adds entropy to the Yarrow generator by incorporating a 64-bit unsigned integer.
+    /// Returns a random 64-bit unsigned integer built from 8 big-endian bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source cannot serve the bytes.
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_be_bytes(bytes)
+    }
+}
+
+/// A reseedable random generator, implemented by `Yarrow` and `Fortuna`.
+///
+/// Extends `RandomSource`, which provides `fill_bytes` and `next_u64`, with reseeding and bounded
+/// draws. The trait is object safe, so applications can swap generators through generics or a
+/// `Box<dyn HorizonRng>` without changing their call sites. The drawing methods panic if the
+/// generator cannot serve bytes, e.g. a `Fortuna` that is not seeded yet; `try_fill_bytes` and
+/// `try_generate_bounded_number` report that case as an error instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::prng::{HorizonRng, Yarrow};
+/// # use horizon::fortuna::Fortuna;
+/// let mut fortuna = Fortuna::new();
+/// fortuna.reseed(b"seed file contents");
+/// let mut generators: Vec<Box<dyn HorizonRng>> = vec![Box::new(Yarrow::new(42)), Box::new(fortuna)];
+/// for rng in &mut generators {
+///     rng.reseed(b"fresh entropy");
+///     println!("{}", rng.generate_bounded_number(1, 6));
+/// }
+/// ```
+pub trait HorizonRng: RandomSource {
+    /// Mixes `seed` into the generator's state.
+    fn reseed(&mut self, seed: &[u8]);
+
+    /// Returns a random 64-bit unsigned integer in `[min, max]`, without modulo bias.
+    ///
+    /// Same as `try_generate_bounded_number`, panicking instead of returning an error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`, or if the generator cannot serve the draws.
+    fn generate_bounded_number(&mut self, min: u64, max: u64) -> u64 {
+        assert!(min <= max, "Invalid range: min ({}) is greater than max ({})", min, max);
+
+        match self.try_generate_bounded_number(min, max) {
+            Ok(random_number) => random_number,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns a random 64-bit unsigned integer in `[min, max]`, without modulo bias, or an error.
+    ///
+    /// Draws below `2^64 mod (max - min + 1)` are rejected, and each one is replaced by a fresh
+    /// 8-byte draw from `try_fill_bytes`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a 64-bit unsigned integer within the range, `PrngError::InvalidRange` if `min` is
+    /// greater than `max`, or the error of `try_fill_bytes` if the generator cannot serve the draws.
+    fn try_generate_bounded_number(&mut self, min: u64, max: u64) -> Result<u64, PrngError> {
+        if min > max {
+            return Err(PrngError::InvalidRange);
+        }
+
+        let mut bytes = [0u8; 8];
+        self.try_fill_bytes(&mut bytes)?;
+        let mut random_number = u64::from_be_bytes(bytes);
+        let Some(span) = (max - min).checked_add(1) else {
+            return Ok(random_number);
+        };
+        let threshold = span.wrapping_neg() % span;
+        while random_number < threshold {
+            self.try_fill_bytes(&mut bytes)?;
+            random_number = u64::from_be_bytes(bytes);
+        }
+
+        Ok(min + (random_number % span))
+    }
+}
+
+/// What a finite byte source does once all of its bytes have been served.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExhaustedPolicy {
+    /// Start again from the first byte.
+    Wrap,
+    /// Fail with `PrngError::Exhausted`.
+    Error,
+}
+
+/// Replays a recorded stream of random bytes through the `RandomSource` interface.
+///
+/// # Fields
+///
+/// - `data`: The recorded bytes.
+/// - `pos`: The position of the next byte to serve.
+/// - `policy`: What to do once `data` has been fully served.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::prng::{ExhaustedPolicy, RandomSource, ReplayRng};
+/// let mut replay = ReplayRng::new(vec![1, 2, 3], ExhaustedPolicy::Wrap);
+/// let mut buf = [0u8; 4];
+/// replay.fill_bytes(&mut buf);
+/// assert_eq!(buf, [1, 2, 3, 1]);
+/// ```
+#[derive(Clone)]
+pub struct ReplayRng {
+    data: Vec<u8>,
+    pos: usize,
+    policy: ExhaustedPolicy,
+}
+
+impl ReplayRng {
+    /// Creates a new `ReplayRng` serving `data` from the start.
     ///
     /// # Parameters
     ///
-    /// - `entropy`: A 64-bit unsigned integer representing the additional entropy.
+    /// - `data`: The recorded bytes to replay.
+    /// - `policy`: What to do once `data` has been fully served.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `ReplayRng` positioned at the first byte.
+    pub fn new(data: Vec<u8>, policy: ExhaustedPolicy) -> Self {
+        ReplayRng { data, pos: 0, policy }
+    }
+
+    /// Creates a new `ReplayRng` whose successive `next_u64` calls return `values` in order.
+    ///
+    /// The values are stored as big-endian bytes, matching `RandomSource::next_u64`, so code generic
+    /// over `RandomSource` can be unit-tested against a scripted sequence of draws.
+    ///
+    /// # Parameters
+    ///
+    /// - `values`: The values to return.
+    /// - `policy`: What to do once every value has been returned.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `ReplayRng` positioned at the first value.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// let mut yarrow_instance = Yarrow::new(42);
-    /// yarrow_instance.add_entropy(123);
+    /// # use horizon::prng::{ExhaustedPolicy, RandomSource, ReplayRng, Yarrow};
+    /// fn roll<R: RandomSource>(rng: &mut R) -> u64 {
+    ///     rng.next_u64() % 6 + 1
+    /// }
+    ///
+    /// let mut scripted = ReplayRng::from_u64s(&[5, 0], ExhaustedPolicy::Wrap);
+    /// assert_eq!([roll(&mut scripted), roll(&mut scripted), roll(&mut scripted)], [6, 1, 6]);
+    /// assert!((1..=6).contains(&roll(&mut Yarrow::new(42))));
     /// ```
-    fn add_entropy(&mut self, entropy: u64) {
-        let entropy_bytes = entropy.to_be_bytes();
-        let mut hasher = Sha3_512::new();
-        hasher.update(entropy_bytes);
-        let hash = hasher.finalize();
-        self.pool.extend(hash.iter().copied());
+    pub fn from_u64s(values: &[u64], policy: ExhaustedPolicy) -> Self {
+        ReplayRng::new(values.iter().flat_map(|value| value.to_be_bytes()).collect(), policy)
+    }
+}
+
+impl RandomSource for ReplayRng {
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), PrngError> {
+        let remaining = self.data.len() - self.pos;
+        match self.policy {
+            ExhaustedPolicy::Error if dest.len() > remaining => return Err(PrngError::Exhausted),
+            ExhaustedPolicy::Wrap if self.data.is_empty() && !dest.is_empty() => return Err(PrngError::Exhausted),
+            _ => {}
+        }
+
+        for byte in dest.iter_mut() {
+            if self.pos == self.data.len() {
+                self.pos = 0;
+            }
+            *byte = self.data[self.pos];
+            self.pos += 1;
+        }
+
+        Ok(())
+    }
+}
+
+/// The fractional part of the golden ratio, used to spread hues around the color wheel.
+#[cfg(feature = "std")]
+const GOLDEN_RATIO_CONJUGATE: f64 = 0.618_033_988_749_895;
+
+/// Counts the `Yarrow` instances created by `new`, so that each one starts from a distinct pool.
+static INSTANCE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Number of requests served by a `ShardedYarrow` between two reseeds of a shard from the master.
+#[cfg(feature = "std")]
+const CROSS_RESEED_INTERVAL: usize = 1024;
+
+/// Size of the entropy pool in bits, matching the SHA3-512 output length.
+const POOL_BITS: u32 = 512;
+
+/// The number of bytes read from the operating system by `from_os_entropy`, enough to fill the pool.
+#[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+const OS_SEED_BYTES: usize = (POOL_BITS / 8) as usize;
+
+/// Number of bytes generated at once to refill the buffer behind `next_byte`.
+const BLOCK_BYTES: usize = 64;
+
+/// Number of Fortuna accumulator pools fed round-robin by `Yarrow::add_entropy_bytes`.
+const FORTUNA_POOLS: usize = 32;
+
+/// Format version written at the start of every `Yarrow::to_snapshot` output.
+const SNAPSHOT_VERSION: u8 = 7;
+
+/// Bits credited by a single source to the fast pool that trigger a fast reseed.
+const FAST_RESEED_BITS: u32 = 100;
+
+/// Bits each source must credit to the slow pool to count towards a slow reseed.
+const SLOW_RESEED_BITS: u32 = 160;
+
+/// Number of sources that must reach `SLOW_RESEED_BITS` to trigger a slow reseed.
+const SLOW_RESEED_SOURCES: usize = 2;
+
+/// The clock a new generator starts with: `None` reads the system clock.
+#[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+const DEFAULT_TIME_COUNTER: Option<u64> = None;
+
+/// The clock a new generator starts with: without a system clock, a counter starting at zero.
+#[cfg(any(feature = "deterministic-only", not(feature = "std")))]
+const DEFAULT_TIME_COUNTER: Option<u64> = Some(0);
+
+/// Number of identical consecutive 64-bit words at which `Yarrow::try_generate_random_bytes` reports
+/// stuck output: the SP 800-90B repetition count cutoff `1 + ceil(20 / 64)` for a false alarm rate of
+/// 2^-20 on full-entropy words.
+const REPETITION_CUTOFF: u32 = 2;
+
+/// Entropy estimate, in bits, from which `Yarrow::is_seeded` considers the generator seeded.
+const SEEDED_BITS: u32 = 256;
+
+/// Number of candidates always evaluated by `Yarrow::generate_bounded_number_ct`.
+const CT_CANDIDATES: usize = 4;
+
+/// The characters of the tokens returned by `Yarrow::generate_token`.
+const TOKEN_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Largest output stored inline, without a heap allocation, by `Yarrow::generate_small`.
+pub const INLINE_BYTES: usize = 32;
+
+mod sealed {
+    /// Keeps `RangeInt` and `RandomValue` from being implemented outside this module.
+    pub trait Sealed {}
+}
+
+macro_rules! impl_sealed {
+    ($($type:ty),*) => {$(
+        impl sealed::Sealed for $type {}
+    )*};
+}
+
+impl_sealed!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, bool, f32, f64);
+
+/// An integer type that `Yarrow::generate_range` can draw.
+///
+/// Implemented for the unsigned and signed integers from 8 to 128 bits, `usize` and `isize`; the
+/// trait is sealed, so no other type can implement it.
+pub trait RangeInt: Copy + PartialOrd + core::fmt::Debug + sealed::Sealed {
+    /// The width of the type in bits.
+    const BITS: u32;
+
+    /// Maps the value to the low `BITS` bits of a `u128`, preserving order: signed values are
+    /// offset by `2^(BITS - 1)`, so the minimum maps to zero.
+    fn to_u128(self) -> u128;
+
+    /// Maps back a `u128` returned by `to_u128`, or any value below `2^BITS`.
+    fn from_u128(value: u128) -> Self;
+}
+
+macro_rules! impl_range_int {
+    ($($int:ty),*) => {$(
+        impl RangeInt for $int {
+            const BITS: u32 = <$int>::BITS;
+
+            fn to_u128(self) -> u128 {
+                self as u128
+            }
+
+            fn from_u128(value: u128) -> Self {
+                value as $int
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_range_int_signed {
+    ($($int:ty => $uint:ty),*) => {$(
+        impl RangeInt for $int {
+            const BITS: u32 = <$int>::BITS;
+
+            fn to_u128(self) -> u128 {
+                ((self as $uint) ^ (1 << (<$int>::BITS - 1))) as u128
+            }
+
+            fn from_u128(value: u128) -> Self {
+                ((value as $uint) ^ (1 << (<$int>::BITS - 1))) as $int
+            }
+        }
+    )*};
+}
+
+impl_range_int!(u8, u16, u32, u64, u128, usize);
+impl_range_int_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+/// A type that `random` can draw uniformly from its whole domain.
+///
+/// Implemented for the primitive integers, `bool`, `f32` and `f64`, the floats being drawn in
+/// `[0, 1)`; the trait is sealed, so no other type can implement it. Every value costs one draw.
+pub trait RandomValue: sealed::Sealed {
+    /// Draws a value from the given generator.
+    fn random_from(yarrow: &mut Yarrow) -> Self;
+}
+
+macro_rules! impl_random_value {
+    ($($int:ty),*) => {$(
+        impl RandomValue for $int {
+            fn random_from(yarrow: &mut Yarrow) -> Self {
+                yarrow.generate_random_number() as $int
+            }
+        }
+    )*};
+}
+
+impl_random_value!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl RandomValue for bool {
+    fn random_from(yarrow: &mut Yarrow) -> Self {
+        yarrow.generate_random_number() >> 63 == 1
+    }
+}
+
+impl RandomValue for f32 {
+    fn random_from(yarrow: &mut Yarrow) -> Self {
+        yarrow.generate_f32()
+    }
+}
+
+impl RandomValue for f64 {
+    fn random_from(yarrow: &mut Yarrow) -> Self {
+        yarrow.generate_f64()
+    }
+}
+
+/// Byte order used to pack generated bytes into words.
+///
+/// # Variants
+///
+/// - `Big`: Most significant byte first, as in `generate_random_number`.
+/// - `Little`: Least significant byte first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Endianness {
+    #[default]
+    Big,
+    Little,
+}
+
+/// Metadata describing a single generation call.
+///
+/// # Fields
+///
+/// - `reseeded`: Whether a reseed updated the seed during the call, whatever its source.
+/// - `hash_ops`: The number of hash (or mixer) invocations performed by the call.
+/// - `entropy_bits_after`: The generator's entropy estimate, in bits, once the call completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GenMetadata {
+    reseeded: bool,
+    hash_ops: u32,
+    entropy_bits_after: u32,
+}
+
+impl GenMetadata {
+    /// Returns whether a reseed updated the seed during the call, whatever its source.
+    pub fn reseeded(&self) -> bool {
+        self.reseeded
+    }
+
+    /// Returns the number of hash (or mixer) invocations performed by the call.
+    pub fn hash_ops(&self) -> u32 {
+        self.hash_ops
+    }
+
+    /// Returns the generator's entropy estimate, in bits, once the call completed.
+    pub fn entropy_bits_after(&self) -> u32 {
+        self.entropy_bits_after
+    }
+}
+
+/// Operational statistics of a generator, returned by `Yarrow::stats`.
+///
+/// # Fields
+///
+/// - `bytes_generated`: The number of bytes generated so far, including bytes buffered by `next_byte`
+///   and not yet served.
+/// - `reseed_count`: The number of reseeds that updated the seed, whatever their source.
+/// - `last_reseed_time`: The time, in seconds, of the last time-gated reseed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct YarrowStats {
+    pub bytes_generated: u64,
+    pub reseed_count: u64,
+    pub last_reseed_time: u64,
+}
+
+/// Outcome of `Yarrow::estimate_period_lower_bound`.
+///
+/// # Variants
+///
+/// - `Cycle`: A generator state repeated; `start` is the step at which the cycle was entered and
+///   `length` the number of steps between the two occurrences.
+/// - `LowerBound`: No state repeated; the period is at least the given number of distinct states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeriodEstimate {
+    Cycle { start: usize, length: usize },
+    LowerBound(usize),
+}
+
+/// What caused a reseed reported by a `YarrowEvent`.
+///
+/// # Variants
+///
+/// - `External`: An explicit call to `reseed`.
+/// - `Output`: The feedback step run after generating output.
+/// - `Interval`: The reseed interval set with `set_reseed_interval` was reached.
+/// - `Fork`: The process ID changed since the last output, i.e. the process was forked.
+/// - `FastPool`: A source credited `FAST_RESEED_BITS` to the fast pool.
+/// - `SlowPool`: Enough sources credited `SLOW_RESEED_BITS` each to the slow pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReseedSource {
+    External,
+    Output,
+    Interval,
+    Fork,
+    FastPool,
+    SlowPool,
+}
+
+/// The entropy one source has credited to the fast and slow pools since their last reseed.
+///
+/// # Fields
+///
+/// - `fast_bits`: The bits credited to the fast pool.
+/// - `slow_bits`: The bits credited to the slow pool.
+/// - `to_slow`: Whether the source's next input goes to the slow pool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct SourceEstimate {
+    fast_bits: u32,
+    slow_bits: u32,
+    to_slow: bool,
+}
+
+/// An event reported to the sink installed with `Yarrow::set_event_sink` (`trace-events` feature).
+///
+/// Without the `trace-events` feature, events are built and dropped in inlined no-op calls, so the
+/// instrumentation costs nothing.
+///
+/// # Variants
+///
+/// - `EntropyAdded`: Entropy was credited; carries the credited `bits` and the resulting estimate.
+/// - `Reseeded`: A reseed updated the seed (see `ReseedSource`); carries its `source` and
+///   the entropy estimate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YarrowEvent {
+    EntropyAdded { bits: u32, entropy_estimate: u32 },
+    Reseeded { source: ReseedSource, entropy_estimate: u32 },
+}
+
+/// A callback receiving the events of a `Yarrow` generator (`trace-events` feature).
+#[cfg(feature = "trace-events")]
+pub type EventSink = Box<dyn FnMut(&YarrowEvent) + Send>;
+
+/// Represents the Yarrow cryptographic pseudorandom number generator.
+///
+/// `Yarrow` deliberately does not implement `Clone`: a clone would emit exactly the same "random"
+/// bytes as the original, e.g. the same keys, and nothing at the call site would show it. Use
+/// `split` to get an independent generator, or `tee` when identical streams are wanted on purpose.
+///
+/// # Fields
+///
+/// - `seed`: A 64-bit unsigned integer representing the initial seed for the generator.
+/// - `pool`: A deque of unsigned 8-bit integers serving as the entropy pool.
+/// - `last_reseed_time`: A 64-bit unsigned integer representing the time of the last reseed operation.
+/// - `mixer`: The `Mixer` used to fold new entropy into the pool.
+/// - `hash_ops`: The total number of hash (or mixer) invocations performed so far.
+/// - `entropy_estimate`: The estimated entropy, in bits, credited from external entropy (capped at the pool size).
+/// - `script`: When set, the bytes replayed in place of generated output (see `deterministic_from`).
+/// - `buffer`: A block of generated bytes not yet served by `next_byte`.
+/// - `buffer_pos`: The position of the next byte to serve from `buffer`.
+/// - `time_counter`: When set, the caller-supplied time used in place of the clock (always set with the `deterministic-only` feature or without `std`).
+/// - `clock`: When set, the `Clock` read in place of the system clock.
+/// - `event_sink`: With the `trace-events` feature, the callback receiving `YarrowEvent`s.
+/// - `rekey_threshold`: When set, the number of generated bytes after which the internal state is rekeyed.
+/// - `bytes_since_rekey`: The number of bytes generated since the last rekey.
+/// - `rekey_count`: The number of rekeys performed so far.
+/// - `reseed_interval`: When set, the number of generated bytes after which the generator reseeds.
+/// - `bytes_since_reseed`: The number of bytes generated since the last interval reseed.
+/// - `draws`: The number of 64-bit draws consumed by the samplers so far (see `draw_count`).
+/// - `health_last`: The last 64-bit word seen by the repetition count test, and how many times in a row it was seen.
+/// - `fortuna_pools`: The `FORTUNA_POOLS` accumulators collecting external entropy until a reseed drains them.
+/// - `fortuna_bits`: The entropy, in bits, waiting in each accumulator; it is credited to `entropy_estimate` when the accumulator is drained.
+/// - `next_pool`: The accumulator fed by the next call to `add_entropy_bytes`.
+/// - `pool_reseeds`: The number of reseeds that drained the accumulators, which decides which of them the next one drains.
+/// - `bytes_generated`: The number of bytes generated so far.
+/// - `reseed_count`: The number of reseeds that updated the seed so far.
+/// - `fast_pool`: The Yarrow fast pool, hashing every other input of each source.
+/// - `slow_pool`: The Yarrow slow pool, hashing the remaining inputs.
+/// - `entropy_sources`: The entropy each source has credited to the fast and slow pools, by source ID.
+/// - `pid`: On Unix with `std`, the ID of the process the generator last produced output in.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let yarrow_instance = Yarrow {
+///     seed: 42,
+///     pool: VecDeque::new(),
+///     last_reseed_time: 0,
+///     mixer: Box::new(Sha3Mixer),
+///     hash_ops: 0,
+///     entropy_estimate: 0,
+///     script: None,
+///     buffer: Vec::new(),
+///     buffer_pos: 0,
+///     time_counter: None,
+///     clock: None,
+///     #[cfg(feature = "trace-events")]
+///     event_sink: None,
+///     rekey_threshold: None,
+///     bytes_since_rekey: 0,
+///     rekey_count: 0,
+///     reseed_interval: None,
+///     bytes_since_reseed: 0,
+///     draws: 0,
+///     health_last: None,
+///     fortuna_pools: vec![Vec::new(); FORTUNA_POOLS],
+///     fortuna_bits: [0; FORTUNA_POOLS],
+///     next_pool: 0,
+///     pool_reseeds: 0,
+///     bytes_generated: 0,
+///     reseed_count: 0,
+///     fast_pool: Vec::new(),
+///     slow_pool: Vec::new(),
+///     entropy_sources: BTreeMap::new(),
+///     #[cfg(all(unix, feature = "std", not(feature = "deterministic-only")))]
+///     pid: std::process::id(),
+/// };
+/// ```
+pub struct Yarrow {
+    seed: u64,
+    pool: VecDeque<u8>,
+    last_reseed_time: u64,
+    mixer: Box<dyn Mixer + Send>,
+    hash_ops: u64,
+    entropy_estimate: u32,
+    script: Option<ReplayRng>,
+    buffer: Vec<u8>,
+    buffer_pos: usize,
+    time_counter: Option<u64>,
+    clock: Option<Box<dyn Clock + Send>>,
+    #[cfg(feature = "trace-events")]
+    event_sink: Option<EventSink>,
+    rekey_threshold: Option<u64>,
+    bytes_since_rekey: u64,
+    rekey_count: u64,
+    reseed_interval: Option<u64>,
+    bytes_since_reseed: u64,
+    draws: u64,
+    health_last: Option<(u64, u32)>,
+    fortuna_pools: Vec<Vec<u8>>,
+    fortuna_bits: [u32; FORTUNA_POOLS],
+    next_pool: usize,
+    pool_reseeds: u64,
+    bytes_generated: u64,
+    reseed_count: u64,
+    fast_pool: Vec<u8>,
+    slow_pool: Vec<u8>,
+    entropy_sources: BTreeMap<u8, SourceEstimate>,
+    #[cfg(all(unix, feature = "std", not(feature = "deterministic-only")))]
+    pid: u32,
+}
+
+/// Implements methods for the Yarrow cryptographic pseudorandom number generator.
+impl Yarrow {
+    /// Creates a new instance of `Yarrow` with the specified seed.
+    ///
+    /// A process-wide instance counter is folded into the pool, so two generators created with the
+    /// same seed in the same process still produce different streams; with `std`, a nonce read from
+    /// the operating system is folded in too, so they also differ across runs. Use
+    /// `new_deterministic` when the stream must be reproducible from the seed alone.
+    ///
+    /// The seed is the only secret input, so a hardcoded or guessable seed makes the output
+    /// predictable: this constructor is meant for testing and simulations. Use `from_os_entropy`
+    /// when the output has to stay secret.
+    ///
+    /// # Parameters
+    ///
+    /// - `seed`: A 64-bit unsigned integer serving as the initial seed for the generator.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `Yarrow` instance with the given seed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let yarrow_instance = Yarrow::new(42);
+    /// ```
+    pub fn new(seed: u64) -> Self {
+        let mut yarrow = Yarrow::with_mixer(seed, Box::new(Sha3Mixer));
+        let instance = INSTANCE_COUNTER.fetch_add(1, Ordering::Relaxed) as u64;
+        yarrow.absorb(instance);
+        #[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+        {
+            let mut nonce = [0u8; 8];
+            if getrandom::getrandom(&mut nonce).is_ok() {
+                yarrow.absorb(u64::from_be_bytes(nonce));
+            }
+        }
+        yarrow
+    }
+
+    /// Creates a new instance of `Yarrow` whose stream depends only on the specified seed.
+    ///
+    /// Two generators created with the same seed produce identical streams, which is useful for
+    /// tests and reproducible simulations but must be avoided when the output has to stay secret.
+    ///
+    /// The system clock is never read: the reseed window runs on a counter that starts at zero and
+    /// only advances through `set_time_counter`, so the same seed and the same sequence of calls
+    /// yield the same bytes on every run.
+    ///
+    /// # Parameters
+    ///
+    /// - `seed`: A 64-bit unsigned integer serving as the initial seed for the generator.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `Yarrow` instance with the given seed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut first = Yarrow::new_deterministic(42);
+    /// let mut second = Yarrow::new_deterministic(42);
+    /// assert_eq!(first.generate_random_bytes(16), second.generate_random_bytes(16));
+    /// ```
+    pub fn new_deterministic(seed: u64) -> Self {
+        let mut yarrow = Yarrow::with_mixer(seed, Box::new(Sha3Mixer));
+        yarrow.time_counter = Some(0);
+        yarrow
+    }
+
+    /// Creates a new instance of `Yarrow` with the specified seed and a custom pool mixer.
+    ///
+    /// The mixer replaces SHA3-512 for the pool and the output blocks only, see `Mixer`.
+    ///
+    /// # Parameters
+    ///
+    /// - `seed`: A 64-bit unsigned integer serving as the initial seed for the generator.
+    /// - `mixer`: The `Mixer` used in place of the default SHA3-512 mixer.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `Yarrow` instance that mixes its pool with `mixer`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::{Sha3Mixer, Yarrow};
+    /// let yarrow_instance = Yarrow::with_mixer(42, Box::new(Sha3Mixer));
+    /// ```
+    pub fn with_mixer(seed: u64, mixer: Box<dyn Mixer + Send>) -> Self {
+        Yarrow {
+            seed,
+            pool: VecDeque::new(),
+            last_reseed_time: 0,
+            mixer,
+            hash_ops: 0,
+            entropy_estimate: 0,
+            script: None,
+            buffer: Vec::new(),
+            buffer_pos: 0,
+            time_counter: DEFAULT_TIME_COUNTER,
+            clock: None,
+            #[cfg(feature = "trace-events")]
+            event_sink: None,
+            rekey_threshold: None,
+            bytes_since_rekey: 0,
+            rekey_count: 0,
+            reseed_interval: None,
+            bytes_since_reseed: 0,
+            draws: 0,
+            health_last: None,
+            fortuna_pools: vec![Vec::new(); FORTUNA_POOLS],
+            fortuna_bits: [0; FORTUNA_POOLS],
+            next_pool: 0,
+            pool_reseeds: 0,
+            bytes_generated: 0,
+            reseed_count: 0,
+            fast_pool: Vec::new(),
+            slow_pool: Vec::new(),
+            entropy_sources: BTreeMap::new(),
+            #[cfg(all(unix, feature = "std", not(feature = "deterministic-only")))]
+            pid: std::process::id(),
+        }
+    }
+
+    /// Creates a new instance of `Yarrow` whose reseed window runs on a custom clock.
+    ///
+    /// Like `new_deterministic`, the stream depends only on the seed, the calls made and the times
+    /// reported by `clock`.
+    ///
+    /// # Parameters
+    ///
+    /// - `seed`: A 64-bit unsigned integer serving as the initial seed for the generator.
+    /// - `clock`: The `Clock` read in place of the system clock.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `Yarrow` instance that reads the time from `clock`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::{Clock, Yarrow};
+    /// #[derive(Clone)]
+    /// struct FixedClock(u64);
+    ///
+    /// impl Clock for FixedClock {
+    ///     fn now_secs(&self) -> u64 {
+    ///         self.0
+    ///     }
+    /// }
+    ///
+    /// let mut yarrow_instance = Yarrow::with_clock(42, Box::new(FixedClock(3600)));
+    /// let random_bytes = yarrow_instance.generate_random_bytes(16);
+    /// ```
+    pub fn with_clock(seed: u64, clock: Box<dyn Clock + Send>) -> Self {
+        let mut yarrow = Yarrow::with_mixer(seed, Box::new(Sha3Mixer));
+        yarrow.time_counter = None;
+        yarrow.clock = Some(clock);
+        yarrow
+    }
+
+    /// Creates a new instance of `Yarrow` whose pool is mixed with the digest `D`.
+    ///
+    /// The pool takes the size of `D`'s output, e.g. 32 bytes with `sha3::Sha3_256`, and
+    /// `Yarrow::with_digest::<Sha3_512>` behaves like `new_deterministic`. The output blocks are
+    /// hashed with `D` too, so they are as long as the digest. Only the pool and the output use `D`:
+    /// the Fortuna accumulators, the fast and slow pools, rekeying and the states derived by `split`
+    /// and `fork_stream` still hash with SHA3-512, the entropy estimate stays capped at 512 bits, and
+    /// `from_snapshot` rejects the snapshots of a generator whose digest is not SHA3-512.
+    ///
+    /// # Parameters
+    ///
+    /// - `seed`: A 64-bit unsigned integer serving as the initial seed for the generator.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `Yarrow` instance that mixes its pool with `D`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::with_digest::<sha3::Sha3_256>(42);
+    /// let random_bytes = yarrow_instance.generate_random_bytes(16);
+    /// ```
+    pub fn with_digest<D: Digest + 'static>(seed: u64) -> Self {
+        let mut yarrow = Yarrow::with_mixer(seed, Box::new(DigestMixer::<D>::new()));
+        yarrow.time_counter = Some(0);
+        yarrow
+    }
+
+    /// Returns a `YarrowBuilder` for a generator with the specified seed.
+    ///
+    /// # Parameters
+    ///
+    /// - `seed`: A 64-bit unsigned integer serving as the initial seed for the generator.
+    ///
+    /// # Returns
+    ///
+    /// Returns a builder with the default configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let yarrow_instance = Yarrow::builder(42).rekey_threshold(1 << 20).build();
+    /// ```
+    pub fn builder(seed: u64) -> YarrowBuilder {
+        YarrowBuilder::new(seed)
+    }
+
+    /// Creates a `Yarrow` in test mode that replays exactly `bytes`, cycling once they run out.
+    ///
+    /// Every generation method then serves the supplied bytes instead of generated output, so code
+    /// consuming randomness can be tested against a predictable sequence through the real API.
+    ///
+    /// # Parameters
+    ///
+    /// - `bytes`: The bytes to replay.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Yarrow` instance in test mode.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::deterministic_from(vec![1, 2, 3]);
+    /// assert_eq!(yarrow_instance.generate_random_bytes(4), vec![1, 2, 3, 1]);
+    /// ```
+    pub fn deterministic_from(bytes: Vec<u8>) -> Self {
+        Yarrow::deterministic_from_with_policy(bytes, ExhaustedPolicy::Wrap)
+    }
+
+    /// Creates a `Yarrow` in test mode that replays exactly `bytes`, handling exhaustion per `policy`.
+    ///
+    /// With `ExhaustedPolicy::Error`, `try_fill_bytes` fails and the other generation methods panic
+    /// once the bytes have been served.
+    ///
+    /// # Parameters
+    ///
+    /// - `bytes`: The bytes to replay.
+    /// - `policy`: What to do once `bytes` have been fully served.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Yarrow` instance in test mode.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::{ExhaustedPolicy, Yarrow};
+    /// let mut yarrow_instance = Yarrow::deterministic_from_with_policy(vec![1, 2], ExhaustedPolicy::Error);
+    /// assert_eq!(yarrow_instance.generate_random_bytes(2), vec![1, 2]);
+    /// ```
+    pub fn deterministic_from_with_policy(bytes: Vec<u8>, policy: ExhaustedPolicy) -> Self {
+        let mut yarrow = Yarrow::new_deterministic(0);
+        yarrow.script = Some(ReplayRng::new(bytes, policy));
+        yarrow
+    }
+
+    /// Creates a new instance of `Yarrow` seeded from the operating system's random number generator.
+    ///
+    /// Reads 64 bytes with `getrandom` (the `getrandom` system call or `/dev/urandom` on Linux,
+    /// `BCryptGenRandom` on Windows): the first eight become the seed, all of them fill the pool,
+    /// and the entropy estimate starts full. The stack copy of the bytes is wiped once they are in
+    /// the pool. The reseed window starts at the current time, so the first output-driven reseed
+    /// happens a minute later. Not available with the `deterministic-only` feature, since the stream
+    /// cannot be reproduced.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `Yarrow` instance, or the error reported by `getrandom` if the operating system
+    /// could not provide random bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::from_os_entropy().expect("OS entropy unavailable");
+    /// let key = yarrow_instance.generate_random_bytes(32);
+    /// ```
+    #[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+    pub fn from_os_entropy() -> Result<Self, getrandom::Error> {
+        let mut bytes = [0u8; OS_SEED_BYTES];
+        getrandom::getrandom(&mut bytes)?;
+
+        let mut seed_bytes = [0u8; 8];
+        seed_bytes.copy_from_slice(&bytes[..8]);
+        let mut yarrow = Yarrow::with_mixer(u64::from_be_bytes(seed_bytes), Box::new(Sha3Mixer));
+        yarrow.pool.extend(bytes.iter().copied());
+        wipe_bytes(&mut bytes);
+        wipe_bytes(&mut seed_bytes);
+        yarrow.entropy_estimate = POOL_BITS;
+        yarrow.last_reseed_time = yarrow.current_time();
+        Ok(yarrow)
+    }
+
+    /// Creates a new instance of `Yarrow` that is seeded from the start, see `is_seeded`.
+    ///
+    /// An alias of `from_os_entropy`, named after `is_seeded` and `generate_when_seeded`: the pool is
+    /// filled from the operating system and the entropy estimate starts full, so
+    /// `generate_when_seeded` never refuses. Not available with the `deterministic-only` feature.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `Yarrow` instance, or the error reported by `getrandom` if the operating system
+    /// could not provide random bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new_seeded().expect("OS entropy unavailable");
+    /// assert!(yarrow_instance.is_seeded());
+    /// let key = yarrow_instance.generate_when_seeded(32)?;
+    /// # Ok::<(), horizon::prngerror::PrngError>(())
+    /// ```
+    #[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+    pub fn new_seeded() -> Result<Self, getrandom::Error> {
+        Yarrow::from_os_entropy()
+    }
+
+    /// Creates a new instance of `Yarrow` seeded from several independent entropy sources.
+    ///
+    /// Each source is hashed with SHA3-512 on its own, then the concatenation of those hashes is hashed
+    /// again to produce the initial pool and seed. Every source therefore contributes through a
+    /// fixed-size digest, and a weak, compromised or all-zero source cannot cancel out the others.
+    ///
+    /// # Parameters
+    ///
+    /// - `sources`: The entropy inputs, e.g. OS randomness, the current time, a user key and a hardware
+    ///   identifier.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `Yarrow` instance seeded from all the sources.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// # use std::time::{SystemTime, UNIX_EPOCH};
+    /// let time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos().to_be_bytes();
+    /// let yarrow_instance = Yarrow::from_sources(&[&time, b"user key", b"00:11:22:33:44:55"]);
+    /// ```
+    pub fn from_sources(sources: &[&[u8]]) -> Self {
+        let mut root_hasher = Sha3_512::new();
+        for source in sources {
+            root_hasher.update(Sha3_512::digest(source));
+        }
+        let root = root_hasher.finalize();
+
+        let mut seed_bytes = [0u8; 8];
+        seed_bytes.copy_from_slice(&root[..8]);
+        let mut yarrow = Yarrow::with_mixer(u64::from_be_bytes(seed_bytes), Box::new(Sha3Mixer));
+        yarrow.pool.extend(root.iter().copied());
+        yarrow.hash_ops += sources.len() as u64 + 1;
+        yarrow
+    }
+
+    /// Adds entropy to the Yarrow generator by incorporating a 64-bit unsigned integer.
+    ///
+    /// Equivalent to `add_entropy_bytes` with the integer's big-endian bytes, so the entropy estimate
+    /// is credited with 64 bits, up to the size of the pool.
+    ///
+    /// # Parameters
+    ///
+    /// - `entropy`: A 64-bit unsigned integer representing the additional entropy.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// yarrow_instance.add_entropy(123);
+    /// ```
+    pub fn add_entropy(&mut self, entropy: u64) {
+        self.add_entropy_bytes(&entropy.to_be_bytes());
+    }
+
+    /// Adds entropy to the Yarrow generator from a byte slice of any length.
+    ///
+    /// As in Fortuna, the slice is not mixed into the generator's pool directly: it is hashed with
+    /// SHA3-512 into one of `FORTUNA_POOLS` accumulators, chosen round-robin, and only reaches the
+    /// output when a reseed drains that accumulator. The `n`-th reseed drains accumulator `i` only
+    /// if `2^i` divides `n`, so accumulator 0 is drained every time, accumulator 1 every other time,
+    /// and so on. An attacker who controls or observes some of the inputs and keeps forcing reseeds
+    /// can at worst starve the low accumulators: the higher ones keep collecting the honest inputs
+    /// spread across them, until one holds enough entropy to recover from a state compromise. The
+    /// entropy estimate is credited with 8 bits per byte, up to the size of the pool, when the
+    /// accumulator is drained.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The bytes carrying the additional entropy.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// yarrow_instance.add_entropy_bytes(&[0x5A; 64]);
+    /// ```
+    pub fn add_entropy_bytes(&mut self, data: &[u8]) {
+        let mut hasher = Sha3_512::new();
+        hasher.update(&self.fortuna_pools[self.next_pool]);
+        hasher.update(data);
+        self.store_accumulator(hasher, data.len());
+    }
+
+    /// Adds entropy to the Yarrow generator from a byte stream, such as an entropy file, a hardware
+    /// device or a socket.
+    ///
+    /// Up to `max_bytes` are read in small chunks and hashed as they arrive, so the stream is never
+    /// held in memory; the result is the same as passing the bytes read to `add_entropy_bytes`. Short
+    /// reads are retried until `max_bytes` have been read or the stream ends, and interrupted reads
+    /// are retried.
+    ///
+    /// # Parameters
+    ///
+    /// - `reader`: The stream to read the entropy from.
+    /// - `max_bytes`: The maximum number of bytes to read.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of bytes absorbed, fewer than `max_bytes` if the stream ended first, or the
+    /// first read error other than `ErrorKind::Interrupted`, in which case nothing is absorbed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let mut source: &[u8] = b"bytes read from an entropy file";
+    /// let absorbed = yarrow_instance.add_entropy_from_reader(&mut source, 16)?;
+    /// assert_eq!(absorbed, 16);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn add_entropy_from_reader<R: Read>(&mut self, reader: &mut R, max_bytes: usize) -> io::Result<usize> {
+        let mut hasher = Sha3_512::new();
+        hasher.update(&self.fortuna_pools[self.next_pool]);
+
+        let mut chunk = [0u8; BLOCK_BYTES];
+        let mut absorbed = 0;
+        while absorbed < max_bytes {
+            let wanted = (max_bytes - absorbed).min(chunk.len());
+            match reader.read(&mut chunk[..wanted]) {
+                Ok(0) => break,
+                Ok(read) => {
+                    hasher.update(&chunk[..read]);
+                    absorbed += read;
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => {
+                    wipe_bytes(&mut chunk);
+                    return Err(error);
+                }
+            }
+        }
+        wipe_bytes(&mut chunk);
+
+        self.store_accumulator(hasher, absorbed);
+        Ok(absorbed)
+    }
+
+    /// Replaces the next Fortuna accumulator with the digest of `hasher`, which has hashed its
+    /// previous content followed by the new input, and records the input's entropy as pending in it.
+    ///
+    /// # Parameters
+    ///
+    /// - `hasher`: The hasher fed with the accumulator and the new input.
+    /// - `len`: The length of the new input, in bytes.
+    fn store_accumulator(&mut self, hasher: Sha3_512, len: usize) {
+        let accumulator = &mut self.fortuna_pools[self.next_pool];
+        wipe_bytes(accumulator);
+        *accumulator = hasher.finalize().to_vec();
+        self.hash_ops += 1;
+        let bits = &mut self.fortuna_bits[self.next_pool];
+        *bits = bits.saturating_add(u32::try_from(len).unwrap_or(u32::MAX).saturating_mul(8));
+        self.next_pool = (self.next_pool + 1) % FORTUNA_POOLS;
+    }
+
+    /// Hashes a byte slice into the pool and credits the entropy estimate with 8 bits per byte.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The bytes carrying the additional entropy.
+    ///
+    /// # Returns
+    ///
+    /// Returns the start of the hash appended to the pool, as returned by `absorb_bytes`.
+    fn credit_entropy(&mut self, data: &[u8]) -> u64 {
+        let digest = self.absorb_bytes(data);
+        self.credit_bits(data.len());
+        digest
+    }
+
+    /// Credits the entropy estimate with 8 bits per byte of external input, up to the pool size.
+    ///
+    /// # Parameters
+    ///
+    /// - `len`: The number of bytes received.
+    fn credit_bits(&mut self, len: usize) {
+        self.credit_estimate(u32::try_from(len).unwrap_or(u32::MAX).saturating_mul(8));
+    }
+
+    /// Credits the entropy estimate with `bits`, up to the pool size.
+    ///
+    /// # Parameters
+    ///
+    /// - `bits`: The number of bits to credit.
+    fn credit_estimate(&mut self, bits: u32) {
+        self.entropy_estimate = self.entropy_estimate.saturating_add(bits).min(POOL_BITS);
+        self.emit(YarrowEvent::EntropyAdded { bits, entropy_estimate: self.entropy_estimate });
+    }
+
+    /// Adds entropy from a registered source through Yarrow's fast and slow pools.
+    ///
+    /// Each source's inputs alternate between the fast and the slow pool, starting with the fast one,
+    /// and the source's estimate is credited to the pool that received the input; the generator's
+    /// entropy estimate only grows when that pool is hashed into the state. As soon as one
+    /// source has credited `FAST_RESEED_BITS` (100) to the fast pool, the fast pool is hashed into
+    /// the state; once `SLOW_RESEED_SOURCES` (2) sources have each credited `SLOW_RESEED_BITS` (160)
+    /// to the slow pool, both pools are. A reseed empties the pools it drained and resets their
+    /// estimates for every source, so a single overestimated source can trigger fast reseeds but
+    /// never a slow one. Unlike the output-driven reseed, these reseeds are not limited to once a
+    /// minute.
+    ///
+    /// # Parameters
+    ///
+    /// - `source_id`: The ID of the entropy source, chosen by the caller.
+    /// - `bytes`: The bytes read from the source.
+    /// - `estimated_bits`: The caller's conservative estimate of the entropy in `bytes`, capped at
+    ///   8 bits per byte.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let jitter = [0x3Au8; 16];
+    /// let interrupts = [0xC5u8; 32];
+    /// yarrow_instance.add_entropy_from_source(0, &jitter, 32);
+    /// yarrow_instance.add_entropy_from_source(1, &interrupts, 128);
+    /// println!("{:?}", yarrow_instance.stats());
+    /// ```
+    pub fn add_entropy_from_source(&mut self, source_id: u8, bytes: &[u8], estimated_bits: u32) {
+        let bits = estimated_bits.min(u32::try_from(bytes.len()).unwrap_or(u32::MAX).saturating_mul(8));
+        let estimate = self.entropy_sources.entry(source_id).or_default();
+        let to_slow = estimate.to_slow;
+        estimate.to_slow = !to_slow;
+        if to_slow {
+            estimate.slow_bits = estimate.slow_bits.saturating_add(bits);
+        } else {
+            estimate.fast_bits = estimate.fast_bits.saturating_add(bits);
+        }
+
+        let pool = if to_slow { &mut self.slow_pool } else { &mut self.fast_pool };
+        let mut hasher = Sha3_512::new();
+        hasher.update(&pool[..]);
+        hasher.update([source_id]);
+        hasher.update(bytes);
+        wipe_bytes(pool);
+        *pool = hasher.finalize().to_vec();
+        self.hash_ops += 1;
+
+        let slow_sources = self.entropy_sources.values().filter(|estimate| estimate.slow_bits >= SLOW_RESEED_BITS).count();
+        if slow_sources >= SLOW_RESEED_SOURCES {
+            self.reseed_from_pools(ReseedSource::SlowPool);
+        } else if self.entropy_sources.values().any(|estimate| estimate.fast_bits >= FAST_RESEED_BITS) {
+            self.reseed_from_pools(ReseedSource::FastPool);
+        }
+    }
+
+    /// Hashes the fast pool, and for a slow reseed the slow pool too, into the state, then empties
+    /// them, credits their per-source estimates to the entropy estimate and resets them.
+    ///
+    /// # Parameters
+    ///
+    /// - `source`: `ReseedSource::FastPool` or `ReseedSource::SlowPool`.
+    fn reseed_from_pools(&mut self, source: ReseedSource) {
+        let slow = source == ReseedSource::SlowPool;
+        let mut input = if slow { b"horizon-yarrow-slow".to_vec() } else { b"horizon-yarrow-fast".to_vec() };
+        input.extend_from_slice(&self.fast_pool);
+        wipe_bytes(&mut self.fast_pool);
+        self.fast_pool.clear();
+        if slow {
+            input.extend_from_slice(&self.slow_pool);
+            wipe_bytes(&mut self.slow_pool);
+            self.slow_pool.clear();
+        }
+        let mut bits = 0u32;
+        for estimate in self.entropy_sources.values_mut() {
+            bits = bits.saturating_add(estimate.fast_bits);
+            estimate.fast_bits = 0;
+            if slow {
+                bits = bits.saturating_add(estimate.slow_bits);
+                estimate.slow_bits = 0;
+            }
+        }
+
+        let mixed = self.mixer.mix(self.pool.make_contiguous(), &input);
+        wipe_bytes(&mut input);
+        self.hash_ops += 1;
+        self.pool = VecDeque::from(mixed);
+        self.seed = self.pool.iter().take(8).fold(0u64, |seed, &byte| (seed << 8) | u64::from(byte));
+        self.reseed_count += 1;
+        self.credit_estimate(bits);
+        self.emit(YarrowEvent::Reseeded { source, entropy_estimate: self.entropy_estimate });
+    }
+
+    /// Drains the Fortuna accumulators due at this reseed into the pool.
+    ///
+    /// The reseed counter is incremented first, then accumulator `i` is drained if `2^i` divides it.
+    /// The drained accumulators are hashed into the pool with the `Mixer` and emptied, and the
+    /// entropy pending in them is credited to the estimate.
+    fn drain_pools(&mut self) {
+        self.pool_reseeds += 1;
+        let due = (self.pool_reseeds.trailing_zeros() as usize + 1).min(FORTUNA_POOLS);
+
+        let mut input = b"horizon-yarrow-fortuna".to_vec();
+        input.extend_from_slice(&self.pool_reseeds.to_be_bytes());
+        for accumulator in &mut self.fortuna_pools[..due] {
+            input.extend_from_slice(accumulator);
+            wipe_bytes(accumulator);
+            accumulator.clear();
+        }
+        let bits = self.fortuna_bits[..due].iter().fold(0u32, |total, &bits| total.saturating_add(bits));
+        self.fortuna_bits[..due].fill(0);
+
+        let mixed = self.mixer.mix(self.pool.make_contiguous(), &input);
+        wipe_bytes(&mut input);
+        self.hash_ops += 1;
+        self.pool = VecDeque::from(mixed);
+        if bits > 0 {
+            self.credit_estimate(bits);
+        }
+    }
+
+    /// Returns the entropy, in bits, added but not yet credited: the bits waiting in the Fortuna
+    /// accumulators and in the fast and slow pools.
+    fn pending_bits(&self) -> u32 {
+        let sources = self.entropy_sources.values().flat_map(|estimate| [estimate.fast_bits, estimate.slow_bits]);
+        self.fortuna_bits.iter().copied().chain(sources).fold(0u32, |total, bits| total.saturating_add(bits))
+    }
+
+    /// Installs a callback receiving the generator's `YarrowEvent`s, e.g. to forward them to a
+    /// logging or tracing system.
+    ///
+    /// # Parameters
+    ///
+    /// - `sink`: The callback invoked for every event.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// yarrow_instance.set_event_sink(Box::new(|event| eprintln!("{:?}", event)));
+    /// ```
+    #[cfg(feature = "trace-events")]
+    pub fn set_event_sink(&mut self, sink: EventSink) {
+        self.event_sink = Some(sink);
+    }
+
+    /// Reports an event to the installed sink, if any.
+    ///
+    /// # Parameters
+    ///
+    /// - `event`: The event to report.
+    #[cfg(feature = "trace-events")]
+    fn emit(&mut self, event: YarrowEvent) {
+        if let Some(sink) = self.event_sink.as_mut() {
+            sink(&event);
+        }
+    }
+
+    /// Discards an event; without the `trace-events` feature there is nowhere to report it.
+    #[cfg(not(feature = "trace-events"))]
+    #[inline(always)]
+    fn emit(&mut self, _event: YarrowEvent) {}
+
+    /// Hashes a 64-bit value into the entropy pool without crediting the entropy estimate.
+    ///
+    /// # Parameters
+    ///
+    /// - `entropy`: A 64-bit unsigned integer to hash into the pool.
+    fn absorb(&mut self, entropy: u64) {
+        self.absorb_bytes(&entropy.to_be_bytes());
+    }
+
+    /// Hashes a byte slice into the entropy pool without crediting the entropy estimate.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The bytes to hash into the pool.
+    ///
+    /// # Returns
+    ///
+    /// Returns the first eight bytes of the hash appended to the pool, as a big-endian integer.
+    fn absorb_bytes(&mut self, data: &[u8]) -> u64 {
+        let hash = Sha3_512::digest(data);
+        self.hash_ops += 1;
+        self.pool.extend(hash.iter().copied());
+        hash.iter().take(8).fold(0u64, |digest, &byte| (digest << 8) | u64::from(byte))
+    }
+
+    /// Folds another generator's entropy pool into this generator's pool.
+    ///
+    /// The other pool, followed by the other generator's Fortuna accumulators and fast and slow pools,
+    /// is mixed in with this
+    /// generator's `Mixer`, and its entropy estimate, along with the entropy still pending in the
+    /// mixed accumulators and pools, is added to this one (capped at the pool size), assuming the two
+    /// generators were fed independent entropy. The seeds are left untouched, so the two
+    /// generators keep producing different streams.
+    ///
+    /// # Parameters
+    ///
+    /// - `other`: The generator whose pool is merged in; it is not modified.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut collector = Yarrow::new(1);
+    /// let mut sensor = Yarrow::new(2);
+    /// sensor.add_entropy(0xDEAD_BEEF);
+    /// collector.merge_entropy_from(&sensor);
+    /// ```
+    pub fn merge_entropy_from(&mut self, other: &Yarrow) {
+        let other_pool: Vec<u8> = other
+            .pool
+            .iter()
+            .chain(other.fortuna_pools.iter().flatten())
+            .chain(&other.fast_pool)
+            .chain(&other.slow_pool)
+            .copied()
+            .collect();
+
+        let mixed = self.mixer.mix(self.pool.make_contiguous(), &other_pool);
+        self.hash_ops += 1;
+        self.pool = VecDeque::from(mixed);
+        let bits = other.entropy_estimate.saturating_add(other.pending_bits());
+        self.entropy_estimate = self.entropy_estimate.saturating_add(bits).min(POOL_BITS);
+    }
+
+    /// Reseeds the Yarrow generator with new entropy, combining external entropy and current system time.
+    ///
+    /// Equivalent to `reseed_bytes` with the integer's big-endian bytes.
+    ///
+    /// # Parameters
+    ///
+    /// - `new_seed`: A 64-bit unsigned integer serving as the new seed for reseeding.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// yarrow_instance.reseed(123);
+    /// ```
+    pub fn reseed(&mut self, new_seed: u64) {
+        self.reseed_bytes(&new_seed.to_be_bytes());
+    }
+
+    /// Reseeds the Yarrow generator from a byte slice of any length.
+    ///
+    /// The slice is credited as entropy like `add_entropy_bytes`, then the time-gated seed update
+    /// folds the start of its hash into the seed.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The bytes carrying the new entropy.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// yarrow_instance.reseed_bytes(b"32 bytes read from a hardware RNG");
+    /// ```
+    pub fn reseed_bytes(&mut self, data: &[u8]) {
+        let new_seed = self.credit_entropy(data);
+        self.refresh_seed(new_seed, ReseedSource::External);
+    }
+
+    /// Stirs the pool and, at most once per minute, drains the Fortuna accumulators that are due and
+    /// folds `new_seed` into the seed.
+    ///
+    /// If the clock has gone backwards since the last reseed (an NTP correction, a VM resumed from a
+    /// snapshot), the pool is still stirred but the seed update is skipped until the clock passes the
+    /// last reseed time again.
+    ///
+    /// # Parameters
+    ///
+    /// - `new_seed`: A 64-bit unsigned integer folded into the seed when the reseed window has elapsed.
+    /// - `source`: What caused the reseed, as reported in the `Reseeded` event.
+    fn refresh_seed(&mut self, new_seed: u64, source: ReseedSource) {
+        let combined_entropy = self.combine_entropy();
+        self.mix_entropy(combined_entropy);
+
+        let current_time = self.current_time();
+        let elapsed = current_time.checked_sub(self.last_reseed_time);
+        if elapsed.is_some_and(|elapsed| elapsed > 60) {
+            self.last_reseed_time = current_time;
+            self.drain_pools();
+            self.seed ^= new_seed;
+            self.reseed_count += 1;
+            self.emit(YarrowEvent::Reseeded { source, entropy_estimate: self.entropy_estimate });
+        }
+    }
+
+    /// Returns the current time in seconds, as used by the reseed window.
+    ///
+    /// Generators created with `new_deterministic` or `with_clock`, or whose time was set with
+    /// `set_time_counter`, never read the system clock. With the `deterministic-only` feature, or
+    /// without `std`, only a custom clock is ever read.
+    ///
+    /// # Returns
+    ///
+    /// Returns the value last passed to `set_time_counter` (zero if none), or else the time reported
+    /// by the custom clock or the `SystemClock`.
+    fn current_time(&self) -> u64 {
+        match (self.time_counter, &self.clock) {
+            (Some(counter), _) => counter,
+            (None, Some(clock)) => clock.now_secs(),
+            #[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+            (None, None) => SystemClock.now_secs(),
+            #[cfg(any(feature = "deterministic-only", not(feature = "std")))]
+            (None, None) => 0,
+        }
+    }
+
+    /// Sets the time, in seconds, seen by the reseed window in place of the system clock.
+    ///
+    /// From then on the generator no longer reads the system clock, nor the clock given to
+    /// `with_clock`.
+    ///
+    /// # Parameters
+    ///
+    /// - `counter`: The current time in seconds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new_deterministic(42);
+    /// yarrow_instance.set_time_counter(120);
+    /// ```
+    pub fn set_time_counter(&mut self, counter: u64) {
+        self.time_counter = Some(counter);
+    }
+
+    /// Combines the current state of the Yarrow generator's entropy pool, seed, and last reseed time.
+    ///
+    /// This is a cheap, non-cryptographic hash: its value is only ever passed to `mix_entropy`, and
+    /// must never be used as output on its own.
+    ///
+    /// # Returns
+    ///
+    /// Returns a 64-bit unsigned integer representing the combined entropy.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let yarrow_instance = Yarrow::new(42);
+    /// let combined_entropy = yarrow_instance.combine_entropy();
+    /// println!("{}", combined_entropy);
+    /// ```
+    fn combine_entropy(&self) -> u64 {
+        let mut combined_entropy = self.seed;
+
+        for byte in &self.pool {
+            combined_entropy = combined_entropy.wrapping_mul(33).wrapping_add(u64::from(*byte));
+        }
+        combined_entropy ^= self.last_reseed_time;
+        combined_entropy
+    }
+
+    /// Mixes additional entropy into the Yarrow generator's entropy pool using the configured `Mixer`
+    /// (SHA3-512 by default).
+    ///
+    /// # Parameters
+    ///
+    /// - `entropy`: A 64-bit unsigned integer representing the additional entropy to be mixed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let additional_entropy = 123;
+    /// yarrow_instance.mix_entropy(additional_entropy);
+    /// ```
+    fn mix_entropy(&mut self, entropy: u64) {
+        let entropy_bytes = entropy.to_be_bytes();
+
+        let mixed = self.mixer.mix(self.pool.make_contiguous(), &entropy_bytes);
+        self.hash_ops += 1;
+        self.pool = VecDeque::from(mixed);
+    }
+
+    /// Generates a sequence of random bytes using the Yarrow generator.
+    ///
+    /// The pool is mixed once, then the output is read from 64-byte blocks, each hashing the pool
+    /// with its block index; the pool is mixed again with the last byte once the output is complete.
+    /// Every output byte is therefore taken from a mixer digest (SHA3-512 by default): the
+    /// multiply-accumulate of `combine_entropy` only feeds the mixer and never reaches the output.
+    /// The cost is one mixer call per 64 bytes plus a constant, so large outputs run at megabytes
+    /// per second in release builds.
+    ///
+    /// # Parameters
+    ///
+    /// - `count`: The number of random bytes to generate.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of unsigned 8-bit integers representing the generated random bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let random_bytes = yarrow_instance.generate_random_bytes(16);
+    /// println!("{:?}", random_bytes);
+    /// ```
+    pub fn generate_random_bytes(&mut self, count: usize) -> Vec<u8> {
+        let mut random_bytes = vec![0u8; count];
+        self.fill(&mut random_bytes);
+
+        random_bytes
+    }
+
+    /// Returns whether enough external entropy has been credited for the output to be used as a secret.
+    ///
+    /// The generator counts as seeded once its entropy estimate reaches 256 bits, i.e. 32 bytes
+    /// passed to `add_entropy_bytes` or `reseed_bytes`. The seed given to `new` is not counted, since
+    /// it may be guessable; `new_seeded` returns a generator that is seeded from the start.
+    /// Entropy added with `add_entropy_bytes` waits in the Fortuna accumulators and is only counted
+    /// once a reseed has drained it into the pool, so a generator reported as seeded never produces
+    /// output that does not depend on that entropy.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the entropy estimate is at least 256 bits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// assert!(!yarrow_instance.is_seeded());
+    /// yarrow_instance.reseed_bytes(&[0x5A; 32]);
+    /// assert!(yarrow_instance.is_seeded());
+    /// ```
+    pub fn is_seeded(&self) -> bool {
+        self.entropy_estimate >= SEEDED_BITS
+    }
+
+    /// Generates a sequence of random bytes, refusing to do so until the generator is seeded.
+    ///
+    /// If the generator is not seeded yet but entropy is waiting in the Fortuna accumulators, the
+    /// accumulators due are drained into the pool first, as at a reseed, and `is_seeded` is checked
+    /// again.
+    ///
+    /// # Parameters
+    ///
+    /// - `count`: The number of random bytes to generate.
+    ///
+    /// # Returns
+    ///
+    /// Returns the generated bytes, or `PrngError::NotSeeded` if `is_seeded` is false, in which case
+    /// nothing is generated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// # use horizon::prngerror::PrngError;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// assert_eq!(yarrow_instance.generate_when_seeded(32), Err(PrngError::NotSeeded));
+    /// ```
+    pub fn generate_when_seeded(&mut self, count: usize) -> Result<Vec<u8>, PrngError> {
+        if !self.is_seeded() && self.fortuna_bits.iter().any(|&bits| bits > 0) {
+            self.drain_pools();
+        }
+        if !self.is_seeded() {
+            return Err(PrngError::NotSeeded);
+        }
+        Ok(self.generate_random_bytes(count))
+    }
+
+    /// Generates a sequence of random bytes, checked by a continuous repetition count test.
+    ///
+    /// Following NIST SP 800-90B, the output is read as 64-bit words and a word seen twice in a row,
+    /// within or across calls, is reported as a health failure:
+    /// a working generator does this with probability 2^-64 per word, a stuck one every time. A
+    /// trailing partial word is not checked.
+    ///
+    /// # Parameters
+    ///
+    /// - `count`: The number of random bytes to generate.
+    ///
+    /// # Returns
+    ///
+    /// Returns the generated bytes, or `PrngError::HealthTestFailed` if the output repeats, in which
+    /// case the bytes must not be used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let random_bytes = yarrow_instance.try_generate_random_bytes(16)?;
+    /// # Ok::<(), horizon::prngerror::PrngError>(())
+    /// ```
+    pub fn try_generate_random_bytes(&mut self, count: usize) -> Result<Vec<u8>, PrngError> {
+        let random_bytes = self.generate_random_bytes(count);
+
+        let mut healthy = true;
+        for chunk in random_bytes.chunks_exact(8) {
+            let mut word_bytes = [0u8; 8];
+            word_bytes.copy_from_slice(chunk);
+            let word = u64::from_be_bytes(word_bytes);
+
+            let run = match self.health_last {
+                Some((last, run)) if last == word => run + 1,
+                _ => 1,
+            };
+            self.health_last = Some((word, run));
+            healthy &= run < REPETITION_CUTOFF;
+        }
+
+        if healthy {
+            Ok(random_bytes)
+        } else {
+            Err(PrngError::HealthTestFailed)
+        }
+    }
+
+    /// Fills a caller-provided buffer with random bytes, without allocating.
+    ///
+    /// The buffer receives exactly the bytes `generate_random_bytes(buf.len())` would have returned,
+    /// so a stack buffer can be reused across many calls. Buffers of any length are accepted; an
+    /// empty one still goes through the feedback step.
+    ///
+    /// # Parameters
+    ///
+    /// - `buf`: The buffer to fill.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let mut key = [0u8; 32];
+    /// yarrow_instance.fill(&mut key);
+    /// println!("{:?}", key);
+    /// ```
+    pub fn fill(&mut self, buf: &mut [u8]) {
+        if let Some(script) = self.script.as_mut() {
+            script.fill_bytes(buf);
+            return;
+        }
+
+        if !buf.is_empty() {
+            self.begin_output().fill(self, buf);
+        }
+        self.finish_output(buf.last().copied().unwrap_or(0));
+    }
+
+    /// Fills a caller-provided buffer with random bytes, without allocating.
+    ///
+    /// Same as `fill`, under the name used by `RandomSource` and `rand_core::RngCore`: as an
+    /// inherent method it needs neither trait in scope, and does not clash when both are imported.
+    ///
+    /// # Parameters
+    ///
+    /// - `dest`: The buffer to fill.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let mut packet = [0u8; 64];
+    /// for _ in 0..4 {
+    ///     yarrow_instance.fill_bytes(&mut packet[..12]);
+    ///     println!("{:?}", &packet[..12]);
+    /// }
+    /// ```
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.fill(dest);
+    }
+
+    /// Generates a fixed-size array of random bytes, without allocating.
+    ///
+    /// The array is filled directly by `fill`, so it holds exactly the bytes
+    /// `generate_random_bytes(N)` would have returned. Any `N` is accepted, including 0 and sizes
+    /// spanning several output blocks.
+    ///
+    /// # Returns
+    ///
+    /// Returns an array of `N` random bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let key: [u8; 32] = yarrow_instance.gen_array();
+    /// let nonce = yarrow_instance.gen_array::<12>();
+    /// println!("{:?} {:?}", key, nonce);
+    /// ```
+    pub fn gen_array<const N: usize>(&mut self) -> [u8; N] {
+        let mut array = [0u8; N];
+        self.fill(&mut array);
+        array
+    }
+
+    /// Mixes the combined entropy into the pool and returns the block stream keyed by the new pool.
+    ///
+    /// The pool is mixed once per output, whatever its length; the bytes themselves then come from
+    /// `output_block`, so producing `n` bytes costs about `n / 64` mixer calls with SHA3-512.
+    ///
+    /// # Returns
+    ///
+    /// Returns an `OutputBlocks` positioned at the first byte of the output.
+    fn begin_output(&mut self) -> OutputBlocks {
+        self.guard_fork();
+        let entropy = self.combine_entropy();
+        self.mix_entropy(entropy);
+        OutputBlocks { counter: 0, block: Vec::new(), pos: 0 }
+    }
+
+    /// Computes one block of output from the pool and a block counter.
+    ///
+    /// The counter is mixed into the pool under the `horizon-yarrow-block` label without updating
+    /// the pool, so the output never reveals the pool itself. The pool is updated by the feedback
+    /// step that ends the output.
+    ///
+    /// # Parameters
+    ///
+    /// - `counter`: The index of the block within the current output.
+    ///
+    /// # Returns
+    ///
+    /// Returns the block, as long as the mixer output (64 bytes with SHA3-512).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mixer returns an empty block.
+    fn output_block(&mut self, counter: u64) -> Vec<u8> {
+        let mut input = b"horizon-yarrow-block".to_vec();
+        input.extend_from_slice(&counter.to_be_bytes());
+
+        let block = self.mixer.mix(self.pool.make_contiguous(), &input);
+        self.hash_ops += 1;
+        assert!(!block.is_empty(), "Mixer returned an empty block");
+        block
+    }
+
+    /// Feeds the last generated byte of an output back into the pool and the seed, then rekeys the
+    /// generator if the rekey threshold has been reached and reseeds it if the reseed interval has.
+    ///
+    /// # Parameters
+    ///
+    /// - `last_byte`: The last byte of the output, or 0 for an empty output.
+    fn finish_output(&mut self, last_byte: u8) {
+        self.absorb(last_byte as u64);
+        self.refresh_seed(last_byte as u64, ReseedSource::Output);
+
+        if self.rekey_threshold.is_some_and(|threshold| self.bytes_since_rekey >= threshold) {
+            self.rekey();
+        }
+        if self.reseed_interval.is_some_and(|interval| self.bytes_since_reseed >= interval) {
+            self.interval_reseed();
+        }
+    }
+
+    /// Reseeds the generator once the reseed interval has been reached.
+    ///
+    /// The Fortuna accumulators that are due are drained, then fresh bytes from the operating system,
+    /// when it can provide them, are mixed into the pool together with the byte counter, and the seed
+    /// is replaced by the start of the new pool. Unlike
+    /// the time-gated update this is not limited to once a minute. Without OS entropy (the
+    /// `deterministic-only` feature, no `std`, or a failing source) the pool is still mixed, so the
+    /// state changes, but no new secret is added. A generator running on the time counter, such as
+    /// one built by `new_deterministic`, never reads OS entropy here, so it stays reproducible.
+    fn interval_reseed(&mut self) {
+        self.drain_pools();
+        let mut input = b"horizon-yarrow-interval".to_vec();
+        input.extend_from_slice(&self.bytes_since_reseed.to_be_bytes());
+        #[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+        if self.time_counter.is_none() {
+            let mut fresh = [0u8; OS_SEED_BYTES];
+            if getrandom::getrandom(&mut fresh).is_ok() {
+                input.extend_from_slice(&fresh);
+                self.entropy_estimate = POOL_BITS;
+            }
+            wipe_bytes(&mut fresh);
+        }
+
+        let mixed = self.mixer.mix(self.pool.make_contiguous(), &input);
+        wipe_bytes(&mut input);
+        self.hash_ops += 1;
+        wipe_bytes(self.pool.make_contiguous());
+        self.pool = VecDeque::from(mixed);
+        self.seed = self.pool.iter().take(8).fold(0u64, |seed, &byte| (seed << 8) | u64::from(byte));
+        self.bytes_since_reseed = 0;
+        self.reseed_count += 1;
+        self.emit(YarrowEvent::Reseeded { source: ReseedSource::Interval, entropy_estimate: self.entropy_estimate });
+    }
+
+    /// Reseeds the generator if the process was forked since its last output.
+    ///
+    /// After `fork()` the parent and the child hold identical copies of the generator and would
+    /// produce the same bytes, e.g. the same keys. Comparing the process ID with the one recorded at
+    /// the last output detects the copy in the child, which then reseeds before serving any byte.
+    #[cfg(all(unix, feature = "std", not(feature = "deterministic-only")))]
+    fn guard_fork(&mut self) {
+        let pid = std::process::id();
+        if pid != self.pid {
+            self.reseed_after_fork(pid);
+        }
+    }
+
+    /// Does nothing: fork detection needs the process ID, only available on Unix with `std`, and a
+    /// reseed from the operating system, unavailable with the `deterministic-only` feature.
+    #[cfg(not(all(unix, feature = "std", not(feature = "deterministic-only"))))]
+    #[inline(always)]
+    fn guard_fork(&mut self) {}
+
+    /// Reseeds the generator from the operating system after a fork into the process `pid`.
+    ///
+    /// The pool is mixed with the new process ID and, when the operating system can provide them,
+    /// fresh random bytes; the seed is replaced by the start of the new pool and bytes buffered for
+    /// `next_byte` before the fork are discarded. Even without OS entropy the child's stream then
+    /// differs from the parent's.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: The ID of the current process.
+    #[cfg(all(unix, feature = "std", not(feature = "deterministic-only")))]
+    fn reseed_after_fork(&mut self, pid: u32) {
+        let mut input = b"horizon-yarrow-fork".to_vec();
+        input.extend_from_slice(&pid.to_be_bytes());
+        let mut fresh = [0u8; OS_SEED_BYTES];
+        if getrandom::getrandom(&mut fresh).is_ok() {
+            input.extend_from_slice(&fresh);
+            self.entropy_estimate = POOL_BITS;
+        }
+        wipe_bytes(&mut fresh);
+
+        let mixed = self.mixer.mix(self.pool.make_contiguous(), &input);
+        wipe_bytes(&mut input);
+        self.hash_ops += 1;
+        self.pool = VecDeque::from(mixed);
+        self.seed = self.pool.iter().take(8).fold(0u64, |seed, &byte| (seed << 8) | u64::from(byte));
+        wipe_bytes(&mut self.buffer);
+        self.buffer_pos = self.buffer.len();
+        self.pid = pid;
+        self.reseed_count += 1;
+        self.emit(YarrowEvent::Reseeded { source: ReseedSource::Fork, entropy_estimate: self.entropy_estimate });
+    }
+
+    /// Sets the number of generated bytes after which the generator reseeds itself.
+    ///
+    /// The check runs at the end of each generation call, so a single call may exceed the interval
+    /// by up to its own length before the reseed happens. Each reseed pulls fresh OS entropy when it
+    /// is available, bounding how much output is produced from the same secret state. Generators
+    /// running on the time counter (`new_deterministic`, `set_time_counter`, or a restored
+    /// deterministic snapshot) skip the OS entropy, so their output stays reproducible.
+    ///
+    /// # Parameters
+    ///
+    /// - `bytes`: The reseed interval in bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// yarrow_instance.set_reseed_interval(1 << 20);
+    /// ```
+    pub fn set_reseed_interval(&mut self, bytes: u64) {
+        assert!(bytes > 0, "Reseed interval must be greater than zero");
+        self.reseed_interval = Some(bytes);
+    }
+
+    /// Replaces the seed and the pool with a fresh internal key derived from the current state.
+    ///
+    /// The new state is the SHA3-512 hash of a domain label, the seed and the pool, so everything
+    /// accumulated so far carries over while the previous state cannot be recovered from the new one.
+    fn rekey(&mut self) {
+        let mut hasher = Sha3_512::new();
+        hasher.update(b"horizon-yarrow-rekey");
+        hasher.update(self.seed.to_be_bytes());
+        hasher.update(self.pool.make_contiguous());
+        let key = hasher.finalize();
+        self.hash_ops += 1;
+
+        let mut seed_bytes = [0u8; 8];
+        seed_bytes.copy_from_slice(&key[..8]);
+        self.seed = u64::from_be_bytes(seed_bytes);
+        self.pool = key.iter().copied().collect();
+        self.bytes_since_rekey = 0;
+        self.rekey_count += 1;
+    }
+
+    /// Returns the number of automatic rekeys performed so far.
+    ///
+    /// # Returns
+    ///
+    /// Returns the rekey count.
+    pub fn rekey_count(&self) -> u64 {
+        self.rekey_count
+    }
+
+    /// Returns the generator's operational statistics.
+    ///
+    /// Useful to check in production that automatic reseeding fires as expected: `reseed_count`
+    /// counts every reseed that updated the seed, whether time-gated, interval-based or after a fork.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `YarrowStats` snapshot of the counters.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// yarrow_instance.generate_random_bytes(16);
+    /// let stats = yarrow_instance.stats();
+    /// assert_eq!(stats.bytes_generated, 16);
+    /// println!("{:?}", stats);
+    /// ```
+    pub fn stats(&self) -> YarrowStats {
+        YarrowStats {
+            bytes_generated: self.bytes_generated,
+            reseed_count: self.reseed_count,
+            last_reseed_time: self.last_reseed_time,
+        }
+    }
+
+    /// Generates `total` random bytes in pieces of at most `chunk` bytes.
+    ///
+    /// Concatenating the pieces gives exactly the output of `generate_random_bytes(total)`, so callers
+    /// can yield to an async executor between pieces without changing the generated stream.
+    ///
+    /// # Parameters
+    ///
+    /// - `total`: The total number of random bytes to generate.
+    /// - `chunk`: The maximum number of bytes in each piece.
+    ///
+    /// # Returns
+    ///
+    /// Returns an iterator over the pieces; only the last one may be shorter than `chunk`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let mut output = Vec::new();
+    /// for piece in yarrow_instance.generate_chunked(1 << 20, 4096) {
+    ///     output.extend(piece);
+    /// }
+    /// ```
+    pub fn generate_chunked(&mut self, total: usize, chunk: usize) -> impl Iterator<Item = Vec<u8>> + '_ {
+        assert!(chunk > 0, "Chunk size must be greater than zero");
+
+        let mut remaining = total;
+        let mut finished = false;
+        let mut stream: Option<OutputBlocks> = None;
+        core::iter::from_fn(move || {
+            if finished {
+                return None;
+            }
+            if let Some(script) = self.script.as_mut() {
+                if remaining == 0 {
+                    finished = true;
+                    return None;
+                }
+                let mut scripted_bytes = vec![0u8; remaining.min(chunk)];
+                script.fill_bytes(&mut scripted_bytes);
+                remaining -= scripted_bytes.len();
+                return Some(scripted_bytes);
+            }
+            if remaining == 0 {
+                // An empty output still goes through the feedback step, as in `generate_random_bytes`.
+                finished = true;
+                self.finish_output(0);
+                return None;
+            }
+
+            let blocks = stream.get_or_insert_with(|| self.begin_output());
+            let piece = blocks.take(self, remaining.min(chunk));
+            remaining -= piece.len();
+            if remaining == 0 {
+                finished = true;
+                self.finish_output(piece[piece.len() - 1]);
+            }
+            Some(piece)
+        })
+    }
+
+    /// Generates a sequence of random bytes, storing small outputs inline instead of in a `Vec`.
+    ///
+    /// Outputs of up to `INLINE_BYTES` bytes, such as keys and tokens, are returned in a fixed-size
+    /// buffer that needs no heap allocation of its own; larger outputs are returned in a `Vec`. The
+    /// bytes are the same as those returned by `generate_random_bytes`.
+    ///
+    /// # Parameters
+    ///
+    /// - `count`: The number of random bytes to generate.
+    ///
+    /// # Returns
+    ///
+    /// Returns the generated bytes as `SmallBytes`, which dereferences to `[u8]`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let token = yarrow_instance.generate_small(16);
+    /// println!("{}", hex::encode(&*token));
+    /// ```
+    pub fn generate_small(&mut self, count: usize) -> SmallBytes {
+        if count > INLINE_BYTES {
+            return SmallBytes::Heap(self.generate_random_bytes(count));
+        }
+
+        let mut bytes = [0u8; INLINE_BYTES];
+        self.fill_bytes(&mut bytes[..count]);
+        SmallBytes::Inline { bytes, len: count }
+    }
+
+    /// Returns an endless iterator over fixed-size frames of random bytes.
+    ///
+    /// Each frame comes with a flag telling whether a reseed updated the seed while the frame was
+    /// generated, be it the time-gated update or the reseed interval set with `set_reseed_interval`,
+    /// so callers can align rekeying with frame boundaries.
+    ///
+    /// # Parameters
+    ///
+    /// - `frame_size`: The number of bytes in each frame.
+    ///
+    /// # Returns
+    ///
+    /// Returns an iterator yielding `(frame, reseeded)` pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// for (frame, reseeded) in yarrow_instance.frame_iter(32).take(3) {
+    ///     println!("{:?} {}", frame, reseeded);
+    /// }
+    /// ```
+    pub fn frame_iter(&mut self, frame_size: usize) -> impl Iterator<Item = (Vec<u8>, bool)> + '_ {
+        core::iter::from_fn(move || {
+            let (frame, metadata) = self.generate_with_metadata(frame_size);
+            Some((frame, metadata.reseeded))
+        })
+    }
+
+    /// Returns the next random byte from the generator's internal block buffer.
+    ///
+    /// Bytes are generated `BLOCK_BYTES` at a time and served one by one, so bytes left in the buffer
+    /// are never discarded: they are returned by the next call to `next_byte` or `iter_bytes`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a random unsigned 8-bit integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let random_byte = yarrow_instance.next_byte();
+    /// println!("{}", random_byte);
+    /// ```
+    pub fn next_byte(&mut self) -> u8 {
+        if self.script.is_some() {
+            return self.generate_random_bytes(1)[0];
+        }
+
+        self.guard_fork();
+        if self.buffer_pos == self.buffer.len() {
+            self.buffer = self.generate_random_bytes(BLOCK_BYTES);
+            self.buffer_pos = 0;
+        }
+
+        let byte = self.buffer[self.buffer_pos];
+        self.buffer_pos += 1;
+        byte
+    }
+
+    /// Returns an endless iterator over random bytes, served from the internal block buffer.
+    ///
+    /// Dropping the iterator part-way through a block leaves the unconsumed bytes buffered for the
+    /// next call to `next_byte` or `iter_bytes`, so no generated bytes are wasted.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `ByteIter` borrowing the generator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let random_bytes: Vec<u8> = yarrow_instance.iter_bytes().take(3).collect();
+    /// println!("{:?}", random_bytes);
+    /// ```
+    pub fn iter_bytes(&mut self) -> ByteIter<'_> {
+        ByteIter { yarrow: self }
+    }
+
+    /// Returns an endless iterator over random 64-bit unsigned integers.
+    ///
+    /// Each item is drawn with `generate_random_number`, so it consumes one draw.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `U64Iter` borrowing the generator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let random_numbers: Vec<u64> = yarrow_instance.iter_u64().take(100).collect();
+    /// assert_eq!(random_numbers.len(), 100);
+    /// ```
+    pub fn iter_u64(&mut self) -> U64Iter<'_> {
+        U64Iter { yarrow: self }
+    }
+
+    /// Borrows the generator as a `RandomSource` limited to `limit` bytes in total.
+    ///
+    /// # Parameters
+    ///
+    /// - `limit`: The maximum number of bytes the wrapper may produce.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `BoundedYarrow` borrowing the generator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::{RandomSource, Yarrow};
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let mut bounded = yarrow_instance.bounded(16);
+    /// let mut input = [0u8; 16];
+    /// assert!(bounded.try_fill_bytes(&mut input).is_ok());
+    /// assert!(bounded.try_fill_bytes(&mut input).is_err());
+    /// ```
+    pub fn bounded(&mut self, limit: usize) -> BoundedYarrow<'_> {
+        BoundedYarrow { yarrow: self, remaining: limit }
+    }
+
+    /// XORs the generator's output over `data` in place, using the generator as a stream cipher.
+    ///
+    /// Two generators created with `new_deterministic` from the same pre-shared seed and at the same
+    /// position produce the same keystream, so applying this method a second time decrypts. Reusing a
+    /// (seed, position) pair for two messages is catastrophic, as with any stream cipher: XORing the two
+    /// ciphertexts cancels the keystream and reveals the XOR of the plaintexts. This mode provides no
+    /// integrity protection either.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The bytes to encrypt or decrypt in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut message = b"attack at dawn".to_vec();
+    /// Yarrow::new_deterministic(42).keystream_xor(&mut message);
+    /// Yarrow::new_deterministic(42).keystream_xor(&mut message);
+    /// assert_eq!(message, b"attack at dawn");
+    /// ```
+    pub fn keystream_xor(&mut self, data: &mut [u8]) {
+        let keystream = self.generate_random_bytes(data.len());
+        for (byte, key) in data.iter_mut().zip(keystream) {
+            *byte ^= key;
+        }
+    }
+
+    /// Generates a sequence of random bytes together with metadata about the generation.
+    ///
+    /// # Parameters
+    ///
+    /// - `count`: The number of random bytes to generate.
+    ///
+    /// # Returns
+    ///
+    /// Returns the generated bytes and a `GenMetadata` describing whether the seed was updated, by
+    /// the time-gated update or by an interval, fork or entropy-pool reseed, how many hash
+    /// invocations ran and the entropy estimate after the call.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let (random_bytes, metadata) = yarrow_instance.generate_with_metadata(16);
+    /// println!("{:?} {:?}", random_bytes, metadata);
+    /// ```
+    pub fn generate_with_metadata(&mut self, count: usize) -> (Vec<u8>, GenMetadata) {
+        let hash_ops_before = self.hash_ops;
+        let reseed_count_before = self.reseed_count;
+
+        let random_bytes = self.generate_random_bytes(count);
+
+        let metadata = GenMetadata {
+            reseeded: self.reseed_count != reseed_count_before,
+            hash_ops: u32::try_from(self.hash_ops - hash_ops_before).unwrap_or(u32::MAX),
+            entropy_bits_after: self.entropy_estimate,
+        };
+
+        (random_bytes, metadata)
+    }
+
+    /// Fills a flat, row-major image buffer with random bytes.
+    ///
+    /// # Parameters
+    ///
+    /// - `buf`: The image buffer, laid out row by row with `channels` bytes per pixel.
+    /// - `width`: The number of pixels per row.
+    /// - `height`: The number of rows.
+    /// - `channels`: The number of bytes per pixel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf.len()` is not exactly `width * height * channels`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let mut image = vec![0u8; 4 * 4 * 3];
+    /// yarrow_instance.fill_image(&mut image, 4, 4, 3);
+    /// ```
+    pub fn fill_image(&mut self, buf: &mut [u8], width: usize, height: usize, channels: usize) {
+        let expected = width.checked_mul(height).and_then(|pixels| pixels.checked_mul(channels));
+        assert_eq!(expected, Some(buf.len()), "Image buffer length does not match width * height * channels");
+
+        self.fill(buf);
+    }
+
+    /// Generates a random RGBA image as a grid of rows of pixels.
+    ///
+    /// # Parameters
+    ///
+    /// - `width`: The number of pixels per row.
+    /// - `height`: The number of rows.
+    ///
+    /// # Returns
+    ///
+    /// Returns `height` rows, each holding `width` RGBA pixels.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let image = yarrow_instance.generate_rgba_image(8, 8);
+    /// println!("{:?}", image[0][0]);
+    /// ```
+    pub fn generate_rgba_image(&mut self, width: usize, height: usize) -> Vec<Vec<[u8; 4]>> {
+        if width == 0 {
+            return vec![Vec::new(); height];
+        }
+
+        let mut buf = vec![0u8; width * height * 4];
+        self.fill_image(&mut buf, width, height, 4);
+
+        buf.chunks_exact(width * 4)
+            .map(|row| row.chunks_exact(4).map(|pixel| [pixel[0], pixel[1], pixel[2], pixel[3]]).collect())
+            .collect()
+    }
+
+    /// Records the output of a single `generate_random_bytes(count)` call for later replay.
+    ///
+    /// # Parameters
+    ///
+    /// - `count`: The number of bytes to record.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `ReplayRng` serving the recorded bytes, failing once they are exhausted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::{RandomSource, Yarrow};
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let mut replay = yarrow_instance.record(32);
+    /// println!("{}", replay.next_u64());
+    /// ```
+    pub fn record(&mut self, count: usize) -> ReplayRng {
+        ReplayRng::new(self.generate_random_bytes(count), ExhaustedPolicy::Error)
+    }
+
+    /// Generates a random 64-bit unsigned integer using the Yarrow generator.
+    ///
+    /// # Returns
+    ///
+    /// Returns a 64-bit unsigned integer representing the generated random number.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let random_number = yarrow_instance.generate_random_number();
+    /// println!("{}", random_number);
+    /// ```
+    pub fn generate_random_number(&mut self) -> u64 {
+        let random_bytes = self.generate_random_bytes(8);
+        self.draws += 1;
+
+        let mut random_number: u64 = 0;
+
+        for &byte in &random_bytes {
+            random_number = (random_number << 8) | u64::from(byte);
+        }
+
+        random_number
+    }
+
+    /// Returns the number of 64-bit draws consumed by the samplers so far.
+    ///
+    /// Every sampler consumes a fixed, documented number of draws per call, so replacing one sampler
+    /// with another of the same cost keeps the rest of the stream aligned. Rejection samplers do not
+    /// read the stream again for a rejected value: it is replaced by a value derived from the secret
+    /// state, so their cost does not depend on the drawn values.
+    ///
+    /// | Sampler | Draws per call |
+    /// |---|---|
+    /// | `generate_random_number`, `gen_bounded_constant_time` | 1 |
+    /// | `generate_bounded_number`, `gen_in`, `gen_bounded_checked`, `gen_index`, `gen_ratio` | 1 |
+    /// | `generate_range` | 1, 2 for 128-bit types |
+    /// | `generate_bounded_number_ct` | 4 |
+    /// | `generate_f64`, `generate_f32`, `generate_laplace`, `generate_log_uniform_duration` | 1 |
+    /// | `gen_bool` | 1 |
+    /// | `generate_fixed_q16_16`, `generate_fixed_q16_16_range` | 1 |
+    /// | `generate_benford_leading_digit`, `generate_benford_number` | 1 |
+    /// | `choose`, `choose_mut`, `weighted_index`, `choose_weighted_index_prefix` | 1 (0 when it returns `None`) |
+    /// | `generate_f64_full` | 2, plus 1 per further 64 leading zero bits |
+    /// | `generate_normal` | 2 |
+    /// | `shuffle`, `random_permutation` | `n - 1` for `n` elements |
+    /// | `iter_u64` | 1 per item |
+    /// | `choose_multiple`, `partial_shuffle` | `k` for `k` elements, `n - 1` when `k` covers all `n` elements |
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of draws consumed since the generator was created.
+    pub fn draw_count(&self) -> u64 {
+        self.draws
+    }
+
+    /// Generates `count` random 64-bit unsigned integers, packed big-endian like
+    /// `generate_random_number`.
+    ///
+    /// # Parameters
+    ///
+    /// - `count`: The number of integers to generate.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector containing the generated integers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let random_numbers = yarrow_instance.generate_many_u64(4);
+    /// println!("{:?}", random_numbers);
+    /// ```
+    pub fn generate_many_u64(&mut self, count: usize) -> Vec<u64> {
+        self.generate_many_u64_with(count, Endianness::default())
+    }
+
+    /// Generates `count` random 64-bit unsigned integers, packing each group of 8 bytes with the
+    /// specified byte order.
+    ///
+    /// All the bytes are generated in a single call, so for the same generator state the words
+    /// produced with `Endianness::Little` are the byte-swapped words produced with `Endianness::Big`.
+    ///
+    /// # Parameters
+    ///
+    /// - `count`: The number of integers to generate.
+    /// - `endianness`: The byte order used to pack each integer.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector containing the generated integers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::{Endianness, Yarrow};
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let random_numbers = yarrow_instance.generate_many_u64_with(4, Endianness::Little);
+    /// println!("{:?}", random_numbers);
+    /// ```
+    pub fn generate_many_u64_with(&mut self, count: usize, endianness: Endianness) -> Vec<u64> {
+        self.generate_random_bytes(count * 8)
+            .chunks_exact(8)
+            .map(|chunk| {
+                let mut word = [0u8; 8];
+                word.copy_from_slice(chunk);
+                match endianness {
+                    Endianness::Big => u64::from_be_bytes(word),
+                    Endianness::Little => u64::from_le_bytes(word),
+                }
+            })
+            .collect()
+    }
+
+    /// Generates a random 64-bit unsigned integer within a specified range using the Yarrow generator.
+    ///
+    /// The result is free of modulo bias: with `n = max - min + 1`, draws below `2^64 mod n` fall in
+    /// the incomplete tail of the range and are rejected, so only the largest multiple of `n` that
+    /// fits in a `u64` is reduced. Each rejected draw is replaced by a value derived from the secret
+    /// state without reading the stream, so the call always consumes exactly one draw. The full range
+    /// `(0, u64::MAX)` returns the draw as is, and `min == max` returns `min`.
+    ///
+    /// # Parameters
+    ///
+    /// - `min`: The minimum value of the generated number (inclusive).
+    /// - `max`: The maximum value of the generated number (inclusive).
+    ///
+    /// # Returns
+    ///
+    /// Returns a 64-bit unsigned integer within the specified range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`; use `try_generate_bounded_number` to get an error
+    /// instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let random_number = yarrow_instance.generate_bounded_number(10, 20);
+    /// println!("{}", random_number);
+    /// ```
+    pub fn generate_bounded_number(&mut self, min: u64, max: u64) -> u64 {
+        assert!(min <= max, "Invalid range: min ({}) is greater than max ({})", min, max);
+
+        let mut random_number = self.generate_random_number();
+        let Some(span) = (max - min).checked_add(1) else {
+            return random_number;
+        };
+        let threshold = span.wrapping_neg() % span;
+        let mut attempt = 0;
+        while random_number < threshold {
+            attempt += 1;
+            random_number = self.redraw(u128::from(random_number), attempt, 64) as u64;
+        }
+
+        min + (random_number % span)
+    }
+
+    /// Generates `n` random 64-bit unsigned integers within a specified range.
+    ///
+    /// The draws are taken from a single output of `8 * n` bytes, so the pool is mixed once for the
+    /// whole batch instead of once per value, then each one goes through the same rejection sampling
+    /// as `generate_bounded_number` and is just as free of modulo bias. Rejected draws are dropped
+    /// and the missing values are taken from a new block, in stream order.
+    ///
+    /// # Parameters
+    ///
+    /// - `min`: The minimum value of the generated numbers (inclusive).
+    /// - `max`: The maximum value of the generated numbers (inclusive).
+    /// - `n`: The number of values to generate.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of `n` integers within the specified range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let rolls = yarrow_instance.generate_bounded_batch(1, 6, 100);
+    /// assert!(rolls.iter().all(|roll| (1..=6).contains(roll)));
+    /// ```
+    pub fn generate_bounded_batch(&mut self, min: u64, max: u64, n: usize) -> Vec<u64> {
+        assert!(min <= max, "Invalid range: min ({}) is greater than max ({})", min, max);
+
+        let Some(span) = (max - min).checked_add(1) else {
+            return self.generate_many_u64(n);
+        };
+        let threshold = span.wrapping_neg() % span;
+        let mut values = Vec::with_capacity(n);
+        while values.len() < n {
+            let block = self.generate_many_u64(n - values.len());
+            values.extend(block.into_iter()
+                .filter(|&random_number| random_number >= threshold)
+                .map(|random_number| min + random_number % span));
+        }
+
+        values
+    }
+
+    /// Generates a random 64-bit unsigned integer within a specified range, reporting inverted
+    /// bounds as an error.
+    ///
+    /// Behaves exactly like `generate_bounded_number` otherwise, and consumes no draw when the
+    /// range is rejected.
+    ///
+    /// # Parameters
+    ///
+    /// - `min`: The minimum value of the generated number (inclusive).
+    /// - `max`: The maximum value of the generated number (inclusive).
+    ///
+    /// # Returns
+    ///
+    /// Returns a 64-bit unsigned integer within the specified range, or
+    /// `PrngError::InvalidRange` if `min` is greater than `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// # use horizon::prngerror::PrngError;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let random_number = yarrow_instance.try_generate_bounded_number(10, 20)?;
+    /// println!("{}", random_number);
+    /// assert_eq!(yarrow_instance.try_generate_bounded_number(20, 10), Err(PrngError::InvalidRange));
+    /// # Ok::<(), horizon::prngerror::PrngError>(())
+    /// ```
+    pub fn try_generate_bounded_number(&mut self, min: u64, max: u64) -> Result<u64, PrngError> {
+        if min > max {
+            return Err(PrngError::InvalidRange);
+        }
+        Ok(self.generate_bounded_number(min, max))
+    }
+
+    /// Generates a random 64-bit unsigned integer within a range expression such as `0..10`,
+    /// `0..=10`, `5..` or `..`.
+    ///
+    /// The bounds are translated into an inclusive minimum and maximum, with an unbounded start or end
+    /// standing for 0 or `u64::MAX`, and the number is drawn with the unbiased
+    /// `generate_bounded_number`.
+    ///
+    /// # Parameters
+    ///
+    /// - `range`: The range to draw from.
+    ///
+    /// # Returns
+    ///
+    /// Returns a 64-bit unsigned integer within the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty, e.g. `5..5`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let die = yarrow_instance.gen_in(1..=6);
+    /// let index = yarrow_instance.gen_in(0..10);
+    /// let any = yarrow_instance.gen_in(..);
+    /// println!("{} {} {}", die, index, any);
+    /// ```
+    pub fn gen_in<R: RangeBounds<u64>>(&mut self, range: R) -> u64 {
+        let min = match range.start_bound() {
+            Bound::Included(&start) => Some(start),
+            Bound::Excluded(&start) => start.checked_add(1),
+            Bound::Unbounded => Some(0),
+        };
+        let max = match range.end_bound() {
+            Bound::Included(&end) => Some(end),
+            Bound::Excluded(&end) => end.checked_sub(1),
+            Bound::Unbounded => Some(u64::MAX),
+        };
+        match (min, max) {
+            (Some(min), Some(max)) if min <= max => self.generate_bounded_number(min, max),
+            _ => panic!("Empty range: {:?}..{:?}", range.start_bound(), range.end_bound()),
+        }
+    }
+
+    /// Generates a random integer of any width and signedness within a specified range.
+    ///
+    /// Each attempt draws as many bytes as the type holds, e.g. a single byte for a `u8` or an `i8`,
+    /// and values below `2^BITS mod n`, with `n = max - min + 1`, are rejected so the result is free
+    /// of modulo bias. As in `generate_bounded_number`, a rejected value is replaced without reading
+    /// the stream, so every call counts as one draw, or two for 128-bit types. Signed ranges
+    /// are drawn through the order-preserving mapping of `RangeInt::to_u128`, so they may span zero
+    /// and the full type.
+    ///
+    /// # Parameters
+    ///
+    /// - `min`: The minimum value of the generated number (inclusive).
+    /// - `max`: The maximum value of the generated number (inclusive).
+    ///
+    /// # Returns
+    ///
+    /// Returns an integer of the same type within the specified range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let die: u8 = yarrow_instance.generate_range(1, 6);
+    /// let port: u16 = yarrow_instance.generate_range(1024, u16::MAX);
+    /// let offset: i32 = yarrow_instance.generate_range(-10, 10);
+    /// let id: u128 = yarrow_instance.generate_range(0, u128::MAX / 3);
+    /// println!("{} {} {} {}", die, port, offset, id);
+    /// ```
+    pub fn generate_range<T: RangeInt>(&mut self, min: T, max: T) -> T {
+        assert!(min <= max, "Invalid range: min ({:?}) is greater than max ({:?})", min, max);
+        self.generate_range_unchecked(min, max)
+    }
+
+    /// Generates a random integer of any width and signedness within a specified range, without
+    /// panicking.
+    ///
+    /// Same as `generate_range`, except that an inverted range is reported as an error.
+    ///
+    /// # Parameters
+    ///
+    /// - `min`: The minimum value of the generated number (inclusive).
+    /// - `max`: The maximum value of the generated number (inclusive).
+    ///
+    /// # Returns
+    ///
+    /// Returns an integer of the same type within the specified range, or `PrngError::InvalidRange`
+    /// if `min` is greater than `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// # use horizon::prngerror::PrngError;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let die: u8 = yarrow_instance.try_generate_range(1, 6)?;
+    /// assert_eq!(yarrow_instance.try_generate_range(6u8, 1), Err(PrngError::InvalidRange));
+    /// # Ok::<(), horizon::prngerror::PrngError>(())
+    /// ```
+    pub fn try_generate_range<T: RangeInt>(&mut self, min: T, max: T) -> Result<T, PrngError> {
+        if min > max {
+            return Err(PrngError::InvalidRange);
+        }
+        Ok(self.generate_range_unchecked(min, max))
+    }
+
+    /// Generates a random integer within `min..=max`, which the caller has checked is not
+    /// inverted; see `generate_range`.
+    fn generate_range_unchecked<T: RangeInt>(&mut self, min: T, max: T) -> T {
+        let mask = u128::MAX >> (128 - T::BITS);
+        let mut random_number = self.draw_range_bits::<T>();
+
+        let range = max.to_u128() - min.to_u128();
+        if range == mask {
+            return T::from_u128(random_number);
+        }
+        let span = range + 1;
+        let threshold = (mask - range) % span;
+        let mut attempt = 0;
+        while random_number < threshold {
+            attempt += 1;
+            random_number = self.redraw(random_number, attempt, T::BITS);
+        }
+        T::from_u128(min.to_u128() + random_number % span)
+    }
+
+    /// Draws `T::BITS` fresh random bits from the stream for `generate_range`.
+    ///
+    /// Types up to 64 bits read as many bytes as they hold; 128-bit types read two
+    /// `generate_random_number` draws, high word first.
+    ///
+    /// # Returns
+    ///
+    /// Returns a value below `2^T::BITS`.
+    fn draw_range_bits<T: RangeInt>(&mut self) -> u128 {
+        if T::BITS > 64 {
+            let high = self.generate_random_number();
+            return (u128::from(high) << 64) | u128::from(self.generate_random_number());
+        }
+
+        let width = (T::BITS / 8) as usize;
+        let mut bytes = [0u8; 16];
+        self.fill(&mut bytes[16 - width..]);
+        self.draws += 1;
+        u128::from_be_bytes(bytes)
+    }
+
+    /// Replaces a value rejected by a rejection sampler without reading the stream.
+    ///
+    /// The replacement is taken from the SHA3-512 hash of a domain label, the seed, the pool, the
+    /// rejected value and the attempt number, so it is as unpredictable as the stream while the
+    /// samplers keep consuming a fixed number of draws, see `draw_count`.
+    ///
+    /// # Parameters
+    ///
+    /// - `rejected`: The rejected value.
+    /// - `attempt`: The number of values rejected so far by the current call, starting at 1.
+    /// - `bits`: The width of the replacement, at most 128.
+    ///
+    /// # Returns
+    ///
+    /// Returns a value below `2^bits`.
+    fn redraw(&mut self, rejected: u128, attempt: u64, bits: u32) -> u128 {
+        let mut hasher = Sha3_512::new();
+        hasher.update(b"horizon-yarrow-redraw");
+        hasher.update(self.seed.to_be_bytes());
+        hasher.update(self.pool.make_contiguous());
+        hasher.update(rejected.to_be_bytes());
+        hasher.update(attempt.to_be_bytes());
+        let digest = hasher.finalize();
+        self.hash_ops += 1;
+
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&digest[..16]);
+        u128::from_be_bytes(bytes) >> (128 - bits)
+    }
+
+    /// Generates a random 64-bit unsigned integer within a specified range in constant time.
+    ///
+    /// Uses Lemire's multiply-high reduction on a single 64-bit draw, without a rejection loop, so the
+    /// running time does not depend on the drawn value. The price is a small residual bias: with
+    /// `n = max - min + 1`, every value has probability `floor(2^64 / n) / 2^64` or
+    /// `ceil(2^64 / n) / 2^64`, so the relative bias of any value is below `n / 2^64` (under 2^-32
+    /// for any range up to 2^32).
+    ///
+    /// # Parameters
+    ///
+    /// - `min`: The minimum value of the generated number (inclusive).
+    /// - `max`: The maximum value of the generated number (inclusive).
+    ///
+    /// # Returns
+    ///
+    /// Returns a 64-bit unsigned integer within the specified range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let pin_digit = yarrow_instance.gen_bounded_constant_time(0, 9);
+    /// println!("{}", pin_digit);
+    /// ```
+    pub fn gen_bounded_constant_time(&mut self, min: u64, max: u64) -> u64 {
+        assert!(min <= max, "Minimum value must not exceed maximum value");
+
+        let random_number = self.generate_random_number();
+        let range = (max - min).wrapping_add(1);
+        if range == 0 {
+            return random_number;
+        }
+
+        min + ((u128::from(random_number) * u128::from(range)) >> 64) as u64
+    }
+
+    /// Generates a random 64-bit unsigned integer within a specified range with a fixed amount of work.
+    ///
+    /// Unlike `gen_bounded_constant_time`, which accepts a small bias to avoid rejection altogether,
+    /// this applies Lemire's multiply-shift reduction with rejection to a fixed number of candidates:
+    /// four independent 64-bit draws, read from the stream in a single output. Every candidate is
+    /// evaluated, and the first accepted one is selected with masks rather than branches, so the
+    /// number of hashes, the draw count and the control flow do not depend on the drawn values.
+    ///
+    /// Guarantees and limits:
+    ///
+    /// - The result is exactly uniform unless all the candidates are rejected, which happens with
+    ///   probability below `(n / 2^64)^4` for `n = max - min + 1`; the last candidate is then used,
+    ///   leaving a bias of that order.
+    /// - The rejection threshold is computed with a division by `n`, whose timing may depend on `n`:
+    ///   the bounds are treated as public, only the drawn value as secret.
+    /// - The selection is written without secret-dependent branches, but the crate does not use a
+    ///   dedicated constant-time library, so the compiler is not prevented from introducing some.
+    ///
+    /// # Parameters
+    ///
+    /// - `min`: The minimum value of the generated number (inclusive).
+    /// - `max`: The maximum value of the generated number (inclusive).
+    ///
+    /// # Returns
+    ///
+    /// Returns a 64-bit unsigned integer within the specified range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let key_index = yarrow_instance.generate_bounded_number_ct(0, 2047);
+    /// println!("{}", key_index);
+    /// ```
+    pub fn generate_bounded_number_ct(&mut self, min: u64, max: u64) -> u64 {
+        assert!(min <= max, "Invalid range: min ({}) is greater than max ({})", min, max);
+
+        let mut bytes = [0u8; 8 * CT_CANDIDATES];
+        self.fill(&mut bytes);
+        self.draws += CT_CANDIDATES as u64;
+        let mut candidates = [0u64; CT_CANDIDATES];
+        for (candidate, chunk) in candidates.iter_mut().zip(bytes.chunks_exact(8)) {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(chunk);
+            *candidate = u64::from_be_bytes(word);
+        }
+
+        let span = (max - min).wrapping_add(1);
+        if span == 0 {
+            return candidates[0];
+        }
+
+        let threshold = span.wrapping_neg() % span;
+        let mut selected = 0u64;
+        let mut found = 0u64;
+        for (index, &candidate) in candidates.iter().enumerate() {
+            let product = u128::from(candidate) * u128::from(span);
+            let high = (product >> 64) as u64;
+            let rejected = u64::from((product as u64).overflowing_sub(threshold).1);
+
+            // Take this candidate if it is accepted and none was before, or if it is the last one.
+            let is_last = u64::from(index == CT_CANDIDATES - 1);
+            let take = ((rejected ^ 1) | is_last) & (found ^ 1);
+            selected |= high & take.wrapping_neg();
+            found |= take;
+        }
+        min + selected
+    }
+
+    /// Generates a random 64-bit unsigned integer within a specified range without ever panicking.
+    ///
+    /// Intended for safety-critical code: invalid input yields `None` instead of a panic. The draw is
+    /// unbiased, using rejection sampling on the values below `2^64 mod (max - min + 1)`; as in
+    /// `generate_bounded_number`, each rejected value is replaced without reading the stream.
+    ///
+    /// # Parameters
+    ///
+    /// - `min`: The minimum value of the generated number (inclusive).
+    /// - `max`: The maximum value of the generated number (inclusive).
+    ///
+    /// # Returns
+    ///
+    /// Returns a 64-bit unsigned integer within the specified range, or `None` if `min` is greater than
+    /// `max` or the random source fails (e.g. a replayed script ran out).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// assert!(yarrow_instance.gen_bounded_checked(10, 20).is_some());
+    /// assert_eq!(yarrow_instance.gen_bounded_checked(20, 10), None);
+    /// ```
+    pub fn gen_bounded_checked(&mut self, min: u64, max: u64) -> Option<u64> {
+        // Panic-freedom: every arithmetic operation below is checked or wrapping, no value is
+        // unwrapped or indexed, and drawing uses `try_fill_bytes`, which reports a failed source as
+        // an error instead of panicking like `fill_bytes`.
+        let range = max.checked_sub(min)?;
+        let span = range.checked_add(1);
+        let threshold = match span {
+            Some(span) => span.wrapping_neg().checked_rem(span)?,
+            None => 0,
+        };
+
+        let mut bytes = [0u8; 8];
+        self.try_fill_bytes(&mut bytes).ok()?;
+        self.draws = self.draws.wrapping_add(1);
+        let mut random_number = u64::from_be_bytes(bytes);
+        let mut attempt = 0u64;
+        while random_number < threshold {
+            attempt = attempt.wrapping_add(1);
+            random_number = self.redraw(u128::from(random_number), attempt, 64) as u64;
+        }
+
+        match span {
+            Some(span) => min.checked_add(random_number.checked_rem(span)?),
+            None => Some(random_number),
+        }
+    }
+
+    /// Generates a uniformly distributed floating-point number in `[0, 1)`.
+    ///
+    /// The value is built from the top 53 bits of a random 64-bit number, matching the precision of
+    /// an `f64` mantissa, so `1.0` can never be returned.
+    ///
+    /// # Returns
+    ///
+    /// Returns a 64-bit floating-point number in the half-open interval `[0, 1)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let value = yarrow_instance.generate_f64();
+    /// println!("{}", value);
+    /// ```
+    pub fn generate_f64(&mut self) -> f64 {
+        (self.generate_random_number() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Generates a uniformly distributed single-precision floating-point number in `[0, 1)`.
+    ///
+    /// The value is built from the top 24 bits of a random 64-bit number, matching the precision of
+    /// an `f32` mantissa, so `1.0` can never be returned.
+    ///
+    /// # Returns
+    ///
+    /// Returns a 32-bit floating-point number in the half-open interval `[0, 1)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let value = yarrow_instance.generate_f32();
+    /// println!("{}", value);
+    /// ```
+    pub fn generate_f32(&mut self) -> f32 {
+        (self.generate_random_number() >> 40) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Generates a random boolean that is `true` with probability `p`.
+    ///
+    /// `p` is clamped to `[0, 1]`. The draw is compared with `generate_f64`, which never returns
+    /// `1.0`, so `gen_bool(0.0)` is always `false` and `gen_bool(1.0)` is always `true`.
+    ///
+    /// # Parameters
+    ///
+    /// - `p`: The probability of returning `true`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` with probability `p`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// if yarrow_instance.gen_bool(0.25) {
+    ///     println!("one time in four");
+    /// }
+    /// ```
+    pub fn gen_bool(&mut self, p: f64) -> bool {
+        assert!(!p.is_nan(), "Probability must not be NaN");
+        self.generate_f64() < p.clamp(0.0, 1.0)
+    }
+
+    /// Generates a random boolean that is `true` with probability `numerator / denominator`.
+    ///
+    /// Unlike `gen_bool`, the probability is exact: the draw is an unbiased integer below
+    /// `denominator`, compared with `numerator`.
+    ///
+    /// # Parameters
+    ///
+    /// - `numerator`: The number of favourable outcomes.
+    /// - `denominator`: The total number of outcomes.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` with probability `numerator / denominator`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `denominator` is zero or smaller than `numerator`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// if yarrow_instance.gen_ratio(1, 3) {
+    ///     println!("one time in three");
+    /// }
+    /// ```
+    pub fn gen_ratio(&mut self, numerator: u32, denominator: u64) -> bool {
+        assert!(
+            denominator != 0 && u64::from(numerator) <= denominator,
+            "Invalid ratio: {}/{}",
+            numerator,
+            denominator
+        );
+        self.generate_bounded_number(0, denominator - 1) < u64::from(numerator)
+    }
+
+    /// Generates a floating-point number in `[0, 1)` using the full precision of an `f64`.
+    ///
+    /// `generate_f64` returns multiples of `2^-53`, so every value below `2^-53` collapses to `0.0` and
+    /// small values carry few significant bits. This method instead treats the random stream as the
+    /// binary expansion of a uniform real number: leading zero bits set the exponent (drawing further
+    /// 64-bit words while they are all zero) and the next 53 bits form the mantissa. Every
+    /// representable float in `[0, 1)`, subnormals included, can then be returned with a probability
+    /// proportional to the interval it covers. Each call consumes two 64-bit draws, plus one more for
+    /// each further 64 zero bits in the vanishingly rare case that they are needed.
+    ///
+    /// # Returns
+    ///
+    /// Returns a 64-bit floating-point number in the half-open interval `[0, 1)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let value = yarrow_instance.generate_f64_full();
+    /// println!("{:e}", value);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn generate_f64_full(&mut self) -> f64 {
+        let mut leading_zeros: i32 = 0;
+        let mut bits = self.generate_random_number();
+        let mut extra_bits = self.generate_random_number();
+        while bits == 0 {
+            leading_zeros += 64;
+            if leading_zeros > 1074 {
+                return 0.0;
+            }
+            bits = extra_bits;
+            extra_bits = self.generate_random_number();
+        }
+
+        let shift = bits.leading_zeros();
+        leading_zeros += shift as i32;
+        let mut mantissa = bits << shift;
+        if shift > 11 {
+            mantissa |= extra_bits >> (64 - shift);
+        }
+
+        // `mantissa >> 11` has exactly 53 significant bits, so the value is below 1.0 and the scaling
+        // by powers of two is exact until it reaches the subnormal range.
+        let mut value = (mantissa >> 11) as f64 * 2f64.powi(-53);
+        while leading_zeros > 1000 {
+            value *= 2f64.powi(-1000);
+            leading_zeros -= 1000;
+        }
+        value * 2f64.powi(-leading_zeros)
+    }
+
+    /// Generates a random fixed-point number in `[0, 1)` in Q16.16 format.
+    ///
+    /// The value is the top 16 bits of a random 64-bit number used as the fractional part. No
+    /// floating-point arithmetic is involved, so the stream is bit-identical on every platform.
+    ///
+    /// # Returns
+    ///
+    /// Returns a Q16.16 value in `[0, 0x10000)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let value = yarrow_instance.generate_fixed_q16_16();
+    /// println!("{}", value as f64 / 65536.0);
+    /// ```
+    pub fn generate_fixed_q16_16(&mut self) -> i32 {
+        (self.generate_random_number() >> 48) as i32
+    }
+
+    /// Generates a random fixed-point number in `[min, max)`, with both bounds in Q16.16 format.
+    ///
+    /// The span is scaled by the top 32 bits of a random 64-bit number using integer arithmetic only.
+    ///
+    /// # Parameters
+    ///
+    /// - `min`: The minimum value, in Q16.16 format (inclusive).
+    /// - `max`: The maximum value, in Q16.16 format (exclusive).
+    ///
+    /// # Returns
+    ///
+    /// Returns a Q16.16 value in `[min, max)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is not less than `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// // A value in [-2.5, 2.5).
+    /// let value = yarrow_instance.generate_fixed_q16_16_range(-0x28000, 0x28000);
+    /// println!("{}", value as f64 / 65536.0);
+    /// ```
+    pub fn generate_fixed_q16_16_range(&mut self, min: i32, max: i32) -> i32 {
+        assert!(min < max, "Minimum value must be less than maximum value");
+
+        let span = (i64::from(max) - i64::from(min)) as u64;
+        let fraction = self.generate_random_number() >> 32;
+        (i64::from(min) + ((span * fraction) >> 32) as i64) as i32
+    }
+
+    /// Generates a random number following a Laplace distribution centered on zero.
+    ///
+    /// Uses the inverse CDF on a uniform value drawn from the open interval `(0, 1)`, so the result is
+    /// always finite. The variance of the distribution is `2 * scale^2`.
+    ///
+    /// # Parameters
+    ///
+    /// - `scale`: The scale parameter `b` of the distribution.
+    ///
+    /// # Returns
+    ///
+    /// Returns the generated noise value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is not strictly positive.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let noisy_count = 120.0 + yarrow_instance.generate_laplace(2.0);
+    /// println!("{}", noisy_count);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn generate_laplace(&mut self, scale: f64) -> f64 {
+        assert!(scale > 0.0, "Scale must be greater than zero");
+
+        let uniform = ((self.generate_random_number() >> 11) as f64 + 0.5) / (1u64 << 53) as f64;
+        if uniform < 0.5 {
+            scale * (2.0 * uniform).ln()
+        } else {
+            -scale * (2.0 * (1.0 - uniform)).ln()
+        }
+    }
+
+    /// Generates a random number following a normal (Gaussian) distribution.
+    ///
+    /// Uses the Box-Muller transform on two `generate_f64` values. The first one is mapped to
+    /// `(0, 1]` before taking its logarithm, so a zero draw yields `ln(1) = 0` instead of an infinite
+    /// value. The second value of the transform is not cached: every call consumes exactly two
+    /// draws, keeping the draw count fixed per call.
+    ///
+    /// # Parameters
+    ///
+    /// - `mean`: The mean of the distribution.
+    /// - `std_dev`: The standard deviation of the distribution.
+    ///
+    /// # Returns
+    ///
+    /// Returns the generated value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `std_dev` is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let height = yarrow_instance.generate_normal(170.0, 8.0);
+    /// println!("{}", height);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn generate_normal(&mut self, mean: f64, std_dev: f64) -> f64 {
+        assert!(std_dev >= 0.0, "Standard deviation must not be negative");
+
+        let radius_uniform = 1.0 - self.generate_f64();
+        let angle_uniform = self.generate_f64();
+        let radius = (-2.0 * radius_uniform.ln()).sqrt();
+        mean + std_dev * radius * (2.0 * core::f64::consts::PI * angle_uniform).cos()
+    }
+
+    /// Generates `n` visually distinct colors.
+    ///
+    /// Hues start at a random point of the color wheel and advance by the golden ratio, which keeps
+    /// them well separated for any `n`. Saturation and lightness are drawn within bounded ranges so
+    /// that no color is washed out or too dark.
+    ///
+    /// # Parameters
+    ///
+    /// - `n`: The number of colors to generate.
+    ///
+    /// # Returns
+    ///
+    /// Returns `n` colors as `(red, green, blue)` tuples.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let palette = yarrow_instance.generate_distinct_palette(5);
+    /// println!("{:?}", palette);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn generate_distinct_palette(&mut self, n: usize) -> Vec<(u8, u8, u8)> {
+        let mut hue = self.generate_f64();
+
+        (0..n).map(|_| {
+            let saturation = 0.5 + 0.4 * self.generate_f64();
+            let lightness = 0.4 + 0.2 * self.generate_f64();
+            let color = hsl_to_rgb(hue, saturation, lightness);
+            hue = (hue + GOLDEN_RATIO_CONJUGATE) % 1.0;
+            color
+        }).collect()
+    }
+
+    /// Estimates the Shannon entropy of the generator's output, in bits per byte.
+    ///
+    /// A sample of `sample_bytes` bytes is generated and the entropy of its byte-value histogram is
+    /// returned. A healthy generator scores close to the maximum of 8.0 on large samples; small
+    /// samples score lower simply because not every byte value gets a chance to appear.
+    ///
+    /// # Parameters
+    ///
+    /// - `sample_bytes`: The number of bytes to generate for the estimate.
+    ///
+    /// # Returns
+    ///
+    /// Returns the estimated entropy in bits per byte, between 0.0 and 8.0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let entropy = yarrow_instance.shannon_entropy_estimate(4096);
+    /// println!("{:.3} bits/byte", entropy);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn shannon_entropy_estimate(&mut self, sample_bytes: usize) -> f64 {
+        if sample_bytes == 0 {
+            return 0.0;
+        }
+
+        let mut histogram = [0usize; 256];
+        for byte in self.generate_random_bytes(sample_bytes) {
+            histogram[byte as usize] += 1;
+        }
+
+        histogram.iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let probability = count as f64 / sample_bytes as f64;
+                -probability * probability.log2()
+            })
+            .sum()
+    }
+
+    /// Searches for a cycle in the generator's state sequence within a bounded number of steps.
+    ///
+    /// Before each of `budget` single-byte generations, a 64-bit hash of the seed, the pool and the
+    /// reseed time is recorded. A repeated hash is reported as a cycle; since only hashes are
+    /// stored, a reported cycle may in principle be a hash collision rather than a true repetition.
+    /// This is an analysis tool for tests and is not meant for production use.
+    ///
+    /// # Parameters
+    ///
+    /// - `budget`: The maximum number of states to observe.
+    ///
+    /// # Returns
+    ///
+    /// Returns the detected cycle, or a lower bound on the period equal to the number of distinct
+    /// states observed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// println!("{:?}", yarrow_instance.estimate_period_lower_bound(10_000));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn estimate_period_lower_bound(&mut self, budget: usize) -> PeriodEstimate {
+        let mut seen = HashMap::with_capacity(budget);
+
+        for step in 0..budget {
+            let mut hasher = DefaultHasher::new();
+            self.seed.hash(&mut hasher);
+            self.pool.hash(&mut hasher);
+            self.last_reseed_time.hash(&mut hasher);
+
+            if let Some(start) = seen.insert(hasher.finish(), step) {
+                return PeriodEstimate::Cycle { start, length: step - start };
+            }
+            self.generate_random_bytes(1);
+        }
+
+        PeriodEstimate::LowerBound(budget)
+    }
+
+    /// Walks a Markov chain described by a transition matrix.
+    ///
+    /// Row `i` of `transition` holds the probabilities of moving from state `i` to every state. At
+    /// each step the next state is drawn from the current state's row.
+    ///
+    /// # Parameters
+    ///
+    /// - `transition`: A square matrix whose rows are probability distributions.
+    /// - `start`: The initial state.
+    /// - `steps`: The number of transitions to simulate.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `steps` states visited after `start`, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square, if a row holds a negative probability or does not sum to
+    /// 1 (within 1e-9), or if `start` is not a valid state.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let transition = vec![vec![0.9, 0.1], vec![0.5, 0.5]];
+    /// let states = yarrow_instance.markov_walk(&transition, 0, 10);
+    /// println!("{:?}", states);
+    /// ```
+    pub fn markov_walk(&mut self, transition: &[Vec<f64>], start: usize, steps: usize) -> Vec<usize> {
+        let states = transition.len();
+        assert!(start < states, "Start state {} is out of range", start);
+        for row in transition {
+            assert_eq!(row.len(), states, "Transition matrix must be square");
+            assert!(row.iter().all(|&p| p >= 0.0), "Transition probabilities must be non-negative");
+            assert!((row.iter().sum::<f64>() - 1.0).abs() < 1e-9, "Transition matrix rows must sum to 1");
+        }
+
+        let mut current = start;
+        (0..steps).map(|_| {
+            let draw = self.generate_f64();
+            let row = &transition[current];
+            let mut cumulative = 0.0;
+            current = row.iter()
+                .position(|&p| {
+                    cumulative += p;
+                    draw < cumulative
+                })
+                .unwrap_or_else(|| row.iter().rposition(|&p| p > 0.0).unwrap_or(current));
+            current
+        }).collect()
+    }
+
+    /// Generates a duration spread log-uniformly between two bounds.
+    ///
+    /// The draw is uniform over the logarithm of the duration, so every order of magnitude between
+    /// `min` and `max` is equally likely, e.g. as many values in `[1ms, 10ms)` as in `[1s, 10s)`.
+    ///
+    /// # Parameters
+    ///
+    /// - `min`: The shortest duration that can be generated (inclusive), must be greater than zero.
+    /// - `max`: The longest duration that can be generated (inclusive).
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Duration` between `min` and `max`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is zero or greater than `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// # use std::time::Duration;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let latency = yarrow_instance.generate_log_uniform_duration(Duration::from_millis(1), Duration::from_secs(10));
+    /// println!("{:?}", latency);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn generate_log_uniform_duration(&mut self, min: Duration, max: Duration) -> Duration {
+        assert!(!min.is_zero(), "Minimum duration must be greater than zero");
+        assert!(min <= max, "Minimum duration must not exceed maximum duration");
+
+        let log_min = min.as_secs_f64().ln();
+        let log_max = max.as_secs_f64().ln();
+        let log_value = log_min + self.generate_f64() * (log_max - log_min);
+
+        Duration::from_secs_f64(log_value.exp()).clamp(min, max)
+    }
+
+    /// Generates a random index in `0..n`.
+    ///
+    /// Draws falling below `2^64 mod n` are rejected and replaced as in `generate_bounded_number`, so
+    /// every index is exactly equally likely, shuffles built on this method are unbiased, and each
+    /// call consumes exactly one draw.
+    ///
+    /// # Parameters
+    ///
+    /// - `n`: The number of possible indices, must be greater than zero.
+    ///
+    /// # Returns
+    ///
+    /// Returns an index lower than `n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let index = yarrow_instance.gen_index(10);
+    /// println!("{}", index);
+    /// ```
+    pub fn gen_index(&mut self, n: usize) -> usize {
+        assert!(n > 0, "Index range must not be empty");
+
+        let span = n as u64;
+        let threshold = span.wrapping_neg() % span;
+        let mut random_number = self.generate_random_number();
+        let mut attempt = 0;
+        while random_number < threshold {
+            attempt += 1;
+            random_number = self.redraw(u128::from(random_number), attempt, 64) as u64;
+        }
+        (random_number % span) as usize
+    }
+
+    /// Shuffles the elements of a mutable slice using the Fisher-Yates algorithm.
+    ///
+    /// The iteration order is a stability guarantee: `i` runs from the last index down to 1, and
+    /// element `i` is swapped with element `generate_bounded_number(0, i)`, which is free of modulo
+    /// bias and equal to `gen_index(i + 1)`. Given the same generator state, the
+    /// same slice is therefore always permuted the same way, and a regression test pins the
+    /// resulting permutation so that any change to this order is caught.
+    ///
+    /// # Parameters
+    ///
+    /// - `items`: A mutable slice of elements to be shuffled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let mut elements = vec![1, 2, 3, 4, 5];
+    /// yarrow_instance.shuffle(&mut elements);
+    /// println!("{:?}", elements);
+    /// ```
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.generate_bounded_number(0, i as u64) as usize;
+            items.swap(i, j);
+        }
+    }
+
+    /// Generates a random permutation of `0..n`.
+    ///
+    /// # Parameters
+    ///
+    /// - `n`: The number of elements to permute.
+    ///
+    /// # Returns
+    ///
+    /// Returns the values `0..n` in random order.
+    pub fn random_permutation(&mut self, n: usize) -> Vec<usize> {
+        let mut permutation: Vec<usize> = (0..n).collect();
+        self.shuffle(&mut permutation);
+        permutation
+    }
+
+    /// Generates a random Latin square of order `n`.
+    ///
+    /// The cyclic square `(row + column) % n` is randomized by permuting its rows, its columns and
+    /// its symbols, each of which preserves the Latin property.
+    ///
+    /// # Parameters
+    ///
+    /// - `n`: The order of the square.
+    ///
+    /// # Returns
+    ///
+    /// Returns an `n` by `n` grid in which every row and every column is a permutation of `0..n`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let square = yarrow_instance.generate_latin_square(4);
+    /// println!("{:?}", square);
+    /// ```
+    pub fn generate_latin_square(&mut self, n: usize) -> Vec<Vec<usize>> {
+        let rows = self.random_permutation(n);
+        let columns = self.random_permutation(n);
+        let symbols = self.random_permutation(n);
+
+        rows.iter()
+            .map(|&row| columns.iter().map(|&column| symbols[(row + column) % n]).collect())
+            .collect()
+    }
+
+    /// Picks a uniformly random element of a slice.
+    ///
+    /// The index is drawn with the unbiased `generate_bounded_number`, consuming exactly one draw; an
+    /// empty slice consumes none.
+    ///
+    /// # Parameters
+    ///
+    /// - `items`: The slice to pick from.
+    ///
+    /// # Returns
+    ///
+    /// Returns a reference to the chosen element, or `None` if the slice is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let colors = ["red", "green", "blue"];
+    /// println!("{:?}", yarrow_instance.choose(&colors));
+    /// ```
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+
+        let index = self.generate_bounded_number(0, items.len() as u64 - 1) as usize;
+        items.get(index)
+    }
+
+    /// Picks a uniformly random element of a slice and returns a mutable reference to it.
+    ///
+    /// Draws the same index as `choose` would.
+    ///
+    /// # Parameters
+    ///
+    /// - `items`: The slice to pick from.
+    ///
+    /// # Returns
+    ///
+    /// Returns a mutable reference to the chosen element, or `None` if the slice is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let mut scores = [0, 0, 0];
+    /// if let Some(score) = yarrow_instance.choose_mut(&mut scores) {
+    ///     *score += 1;
+    /// }
+    /// assert_eq!(scores.iter().sum::<i32>(), 1);
+    /// ```
+    pub fn choose_mut<'a, T>(&mut self, items: &'a mut [T]) -> Option<&'a mut T> {
+        if items.is_empty() {
+            return None;
+        }
+
+        let index = self.generate_bounded_number(0, items.len() as u64 - 1) as usize;
+        items.get_mut(index)
+    }
+
+    /// Shuffles only the first `k` positions of a slice, e.g. to draw 3 winners out of 10000 entries.
+    ///
+    /// Runs the first `k` steps of a forward Fisher-Yates shuffle in place: step `i` swaps position
+    /// `i` with a position drawn with `generate_bounded_number(i, len - 1)`, so the prefix is a
+    /// uniform random sample of the slice in random order, at the cost of `k` draws instead of
+    /// `len - 1`. When `k` covers the whole slice, the slice is fully shuffled with `shuffle`.
+    ///
+    /// # Parameters
+    ///
+    /// - `items`: The slice to partially shuffle.
+    /// - `k`: The number of positions to shuffle.
+    ///
+    /// # Returns
+    ///
+    /// Returns the shuffled prefix of `min(k, items.len())` elements and the remainder of the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let mut entries: Vec<u32> = (0..10_000).collect();
+    /// let (winners, _) = yarrow_instance.partial_shuffle(&mut entries, 3);
+    /// assert_eq!(winners.len(), 3);
+    /// ```
+    pub fn partial_shuffle<'a, T>(&mut self, items: &'a mut [T], k: usize) -> (&'a mut [T], &'a mut [T]) {
+        if k >= items.len() {
+            self.shuffle(items);
+            return items.split_at_mut(items.len());
+        }
+
+        let last = items.len() as u64 - 1;
+        for i in 0..k {
+            let j = self.generate_bounded_number(i as u64, last) as usize;
+            items.swap(i, j);
+        }
+        items.split_at_mut(k)
+    }
+
+    /// Picks `k` distinct elements of a slice, sampling without replacement.
+    ///
+    /// Runs `partial_shuffle` on a copy of the slice and keeps the prefix, so every `k`-subset is
+    /// equally likely, and so is every order within it. Elements are distinct by position, so equal
+    /// values in the input may appear more than once.
+    ///
+    /// # Parameters
+    ///
+    /// - `items`: The slice to sample from.
+    /// - `k`: The number of elements to pick.
+    ///
+    /// # Returns
+    ///
+    /// Returns `min(k, items.len())` elements in random order; when `k` covers the whole slice, this
+    /// is a shuffled copy of it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let members = ["alice", "bob", "carol", "dave", "erin"];
+    /// let committee = yarrow_instance.choose_multiple(&members, 3);
+    /// assert_eq!(committee.len(), 3);
+    /// ```
+    pub fn choose_multiple<T: Clone>(&mut self, items: &[T], k: usize) -> Vec<T> {
+        let mut pool = items.to_vec();
+        let chosen = self.partial_shuffle(&mut pool, k).0.len();
+        pool.truncate(chosen);
+        pool
+    }
+
+    /// Picks a random element of an ordered set, reproducibly.
+    ///
+    /// Because a `BTreeSet` iterates in sorted order, the same seed always selects the same element.
+    /// There is no equivalent for `HashSet`: its iteration order is randomized per process, so the
+    /// element found at a given position changes from run to run whatever the generator does.
+    ///
+    /// # Parameters
+    ///
+    /// - `set`: The set to pick from.
+    ///
+    /// # Returns
+    ///
+    /// Returns a reference to the chosen element, or `None` if the set is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// # use std::collections::BTreeSet;
+    /// let mut yarrow_instance = Yarrow::new_deterministic(42);
+    /// let set: BTreeSet<&str> = ["alice", "bob", "carol"].into_iter().collect();
+    /// println!("{:?}", yarrow_instance.choose_stable(&set));
+    /// ```
+    pub fn choose_stable<'a, T: Ord>(&mut self, set: &'a BTreeSet<T>) -> Option<&'a T> {
+        if set.is_empty() {
+            return None;
+        }
+
+        let index = self.generate_bounded_number(0, set.len() as u64 - 1) as usize;
+        set.iter().nth(index)
+    }
+
+    /// Splits the generator into `n` independent, labeled child generators.
+    ///
+    /// Like `split`, each child's pool is the SHA3-512 hash of a domain label, the child's index, and
+    /// this generator's seed and pool, so children never share a stream and each holds a full 64-byte
+    /// state. This generator's pool is then replaced by the hash of the same state under the
+    /// `horizon-yarrow-split-streams-parent` label. The parent advances deterministically, so a
+    /// parent created with `new_deterministic` always splits into the same children. Like `split` and
+    /// `fork_stream`, each child keeps the mixer, the clock and the entropy estimate.
+    ///
+    /// # Parameters
+    ///
+    /// - `n`: The number of child generators to create.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `n` child generators, in label order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let mut children = yarrow_instance.split_streams(4);
+    /// println!("{}", children[0].generate_random_number());
+    /// ```
+    pub fn split_streams(&mut self, n: usize) -> Vec<Yarrow> {
+        let children = (0..n as u64)
+            .map(|index| {
+                let mut label = b"horizon-yarrow-split-streams".to_vec();
+                label.extend_from_slice(&index.to_be_bytes());
+                let child_state = self.split_state(&label);
+                self.derive_child(&child_state)
+            })
+            .collect();
+
+        let parent_state = self.split_state(b"horizon-yarrow-split-streams-parent");
+        self.set_state(&parent_state);
+        children
+    }
+
+    /// Derives the child generator of a numbered stream, e.g. one per worker of a parallel simulation.
+    ///
+    /// The child's pool is the SHA3-512 hash of a domain label, this generator's seed and pool, and
+    /// `stream_id`, and its seed is the start of that hash. Different ids therefore give unrelated
+    /// streams, while the same id always gives the same child as long as this generator's state is
+    /// unchanged: deriving a stream does not advance it. Unlike `split_streams`, children can be
+    /// derived in any order, or on different machines from a copy of the same state.
+    ///
+    /// # Parameters
+    ///
+    /// - `stream_id`: The number of the stream to derive.
+    ///
+    /// # Returns
+    ///
+    /// Returns the child generator, which keeps this generator's mixer, clock and entropy estimate,
+    /// like the children of `split`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut master = Yarrow::new_deterministic(42);
+    /// let mut worker = master.fork_stream(3);
+    /// assert_eq!(worker.generate_random_bytes(16), master.fork_stream(3).generate_random_bytes(16));
+    /// ```
+    pub fn fork_stream(&mut self, stream_id: u64) -> Yarrow {
+        let mut hasher = Sha3_512::new();
+        hasher.update(b"horizon-yarrow-stream");
+        hasher.update(self.seed.to_be_bytes());
+        hasher.update(self.pool.make_contiguous());
+        hasher.update(stream_id.to_be_bytes());
+        let state = hasher.finalize();
+        self.hash_ops += 1;
+        self.derive_child(&state)
+    }
+
+    /// Splits off an independent child generator, advancing this one.
+    ///
+    /// This is the safe replacement for `Clone`. The child's pool is the SHA3-512 hash of the
+    /// `horizon-yarrow-split` label, this generator's seed and pool, and this generator's pool is
+    /// replaced by the hash of the same state under the `horizon-yarrow-split-parent` label. Both
+    /// therefore diverge immediately, and neither can recover the other's state. Bytes already
+    /// buffered by `next_byte` stay with this generator. The child keeps the mixer, the clock and the
+    /// entropy estimate, but starts with empty Fortuna accumulators and fresh counters. Use
+    /// `fork_stream` instead to derive numbered children without advancing this generator.
+    ///
+    /// # Returns
+    ///
+    /// Returns the child generator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut parent = Yarrow::new(42);
+    /// let mut child = parent.split();
+    /// assert_ne!(parent.generate_random_bytes(16), child.generate_random_bytes(16));
+    /// ```
+    pub fn split(&mut self) -> Yarrow {
+        let child_state = self.split_state(b"horizon-yarrow-split");
+        let parent_state = self.split_state(b"horizon-yarrow-split-parent");
+
+        let child = self.derive_child(&child_state);
+        self.set_state(&parent_state);
+        child
+    }
+
+    /// Builds a child generator from a derived state, for `split`, `split_streams` and `fork_stream`.
+    ///
+    /// The child keeps this generator's mixer, time counter, clock, entropy estimate and reseed
+    /// window, but starts with empty Fortuna accumulators and fresh counters.
+    ///
+    /// # Parameters
+    ///
+    /// - `state`: The child's pool, at least 8 bytes long; its first 8 bytes become the seed.
+    fn derive_child(&self, state: &[u8]) -> Yarrow {
+        let mut child = Yarrow::with_mixer(0, self.mixer.clone_box());
+        child.set_state(state);
+        child.time_counter = self.time_counter;
+        child.clock = self.clock.as_ref().map(|clock| clock.clone_box());
+        child.entropy_estimate = self.entropy_estimate;
+        child.last_reseed_time = self.last_reseed_time;
+        child
+    }
+
+    /// Hashes a domain label with the seed and the pool, for `split` and `split_streams`.
+    ///
+    /// # Parameters
+    ///
+    /// - `label`: The domain separation label.
+    ///
+    /// # Returns
+    ///
+    /// Returns the 64-byte SHA3-512 digest.
+    fn split_state(&mut self, label: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha3_512::new();
+        hasher.update(label);
+        hasher.update(self.seed.to_be_bytes());
+        hasher.update(self.pool.make_contiguous());
+        self.hash_ops += 1;
+        hasher.finalize().to_vec()
+    }
+
+    /// Replaces the pool with `state` and the seed with its first 8 bytes.
+    ///
+    /// # Parameters
+    ///
+    /// - `state`: The new pool, at least 8 bytes long.
+    fn set_state(&mut self, state: &[u8]) {
+        let mut seed_bytes = [0u8; 8];
+        seed_bytes.copy_from_slice(&state[..8]);
+        self.seed = u64::from_be_bytes(seed_bytes);
+        self.pool = state.iter().copied().collect();
+    }
+
+    /// Shuffles `0..n` `trials` times and counts where each value lands, to check the shuffle for bias.
+    ///
+    /// For an unbiased shuffle, every cell of the matrix is close to `trials / n`.
+    ///
+    /// # Parameters
+    ///
+    /// - `n`: The number of elements to shuffle.
+    /// - `trials`: The number of shuffles to perform.
+    ///
+    /// # Returns
+    ///
+    /// Returns an `n` x `n` matrix whose cell `[value][position]` counts how often `value` ended up at
+    /// `position`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let report = yarrow_instance.shuffle_bias_report(52, 10_000);
+    /// println!("{:?}", report[0]);
+    /// ```
+    pub fn shuffle_bias_report(&mut self, n: usize, trials: usize) -> Vec<Vec<usize>> {
+        let mut counts = vec![vec![0usize; n]; n];
+        let mut items: Vec<usize> = (0..n).collect();
+
+        for _ in 0..trials {
+            items.iter_mut().enumerate().for_each(|(i, item)| *item = i);
+            self.shuffle(&mut items);
+            for (position, &value) in items.iter().enumerate() {
+                counts[value][position] += 1;
+            }
+        }
+
+        counts
+    }
+
+    /// Returns two exact copies of the generator's current state.
+    ///
+    /// Unlike `split_streams`, which derives independent streams, both copies produce the same stream
+    /// as each other (and as this generator would), which is useful to feed two code paths identical
+    /// randomness. The copies are independent afterwards: advancing one does not affect the other. An
+    /// event sink installed with `set_event_sink` is not copied.
+    ///
+    /// Identical streams must never be used where the output has to stay secret.
+    ///
+    /// # Returns
+    ///
+    /// Returns the two copies.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let yarrow_instance = Yarrow::new(42);
+    /// let (mut left, mut right) = yarrow_instance.tee();
+    /// assert_eq!(left.generate_random_bytes(16), right.generate_random_bytes(16));
+    /// ```
+    pub fn tee(&self) -> (Yarrow, Yarrow) {
+        (self.duplicate(), self.duplicate())
+    }
+
+    /// Returns an exact copy of the generator's state, without any event sink.
+    fn duplicate(&self) -> Yarrow {
+        Yarrow {
+            seed: self.seed,
+            pool: self.pool.clone(),
+            last_reseed_time: self.last_reseed_time,
+            mixer: self.mixer.clone_box(),
+            hash_ops: self.hash_ops,
+            entropy_estimate: self.entropy_estimate,
+            script: self.script.clone(),
+            buffer: self.buffer.clone(),
+            buffer_pos: self.buffer_pos,
+            time_counter: self.time_counter,
+            clock: self.clock.as_ref().map(|clock| clock.clone_box()),
+            #[cfg(feature = "trace-events")]
+            event_sink: None,
+            rekey_threshold: self.rekey_threshold,
+            bytes_since_rekey: self.bytes_since_rekey,
+            rekey_count: self.rekey_count,
+            reseed_interval: self.reseed_interval,
+            bytes_since_reseed: self.bytes_since_reseed,
+            draws: self.draws,
+            health_last: self.health_last,
+            fortuna_pools: self.fortuna_pools.clone(),
+            fortuna_bits: self.fortuna_bits,
+            next_pool: self.next_pool,
+            pool_reseeds: self.pool_reseeds,
+            bytes_generated: self.bytes_generated,
+            reseed_count: self.reseed_count,
+            fast_pool: self.fast_pool.clone(),
+            slow_pool: self.slow_pool.clone(),
+            entropy_sources: self.entropy_sources.clone(),
+            #[cfg(all(unix, feature = "std", not(feature = "deterministic-only")))]
+            pid: self.pid,
+        }
+    }
+
+    /// Serializes the generator's state into a compact snapshot.
+    ///
+    /// The snapshot holds the seed, the pool, the Fortuna accumulators, the last reseed time and the
+    /// counters that drive rekeying and metadata, so `from_snapshot` resumes the exact same stream.
+    /// Integers are stored big-endian, and the pool, the `next_byte` buffer and each accumulator as a
+    /// 32-bit length followed by their raw bytes. The mixer, a custom clock, a replayed script and an event sink are not captured: a
+    /// restored generator always uses SHA3-512 and, unless its time was set, the system clock. The
+    /// snapshot only records whether the mixer mixes like `Sha3Mixer`, and `from_snapshot` rejects
+    /// it otherwise, since the restored stream would diverge.
+    ///
+    /// The stream only stays identical if the restored generator sees the same reseed window, e.g. it
+    /// is restored within the same minute or its time was set with `set_time_counter` (as with
+    /// `new_deterministic`), in which case the clock is part of the snapshot.
+    ///
+    /// # Returns
+    ///
+    /// Returns the encoded state.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let snapshot = yarrow_instance.to_snapshot();
+    /// let expected = yarrow_instance.generate_random_bytes(16);
+    ///
+    /// let mut restored = Yarrow::from_snapshot(&snapshot)?;
+    /// assert_eq!(restored.generate_random_bytes(16), expected);
+    /// # Ok::<(), horizon::prngerror::PrngError>(())
+    /// ```
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        let mut bytes = vec![SNAPSHOT_VERSION];
+        for value in [
+            self.seed,
+            self.last_reseed_time,
+            self.hash_ops,
+            u64::from(self.entropy_estimate),
+            self.rekey_threshold.unwrap_or(0),
+            self.bytes_since_rekey,
+            self.rekey_count,
+            self.reseed_interval.unwrap_or(0),
+            self.bytes_since_reseed,
+            self.draws,
+            self.time_counter.unwrap_or(0),
+            self.next_pool as u64,
+            self.pool_reseeds,
+            self.bytes_generated,
+            self.reseed_count,
+        ] {
+            bytes.extend_from_slice(&value.to_be_bytes());
+        }
+        bytes.push(u8::from(self.time_counter.is_some()));
+        bytes.push(u8::from(!self.mixer.is_sha3_512()));
+        bytes.extend_from_slice(&(self.pool.len() as u32).to_be_bytes());
+        bytes.extend(self.pool.iter());
+        bytes.extend_from_slice(&(self.buffer.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&self.buffer);
+        bytes.extend_from_slice(&(self.buffer_pos as u32).to_be_bytes());
+        for (accumulator, bits) in self.fortuna_pools.iter().zip(self.fortuna_bits) {
+            bytes.extend_from_slice(&(accumulator.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(accumulator);
+            bytes.extend_from_slice(&bits.to_be_bytes());
+        }
+        for pool in [&self.fast_pool, &self.slow_pool] {
+            bytes.extend_from_slice(&(pool.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(pool);
+        }
+        bytes.extend_from_slice(&(self.entropy_sources.len() as u32).to_be_bytes());
+        for (&source_id, estimate) in &self.entropy_sources {
+            bytes.push(source_id);
+            bytes.extend_from_slice(&estimate.fast_bits.to_be_bytes());
+            bytes.extend_from_slice(&estimate.slow_bits.to_be_bytes());
+            bytes.push(u8::from(estimate.to_slow));
+        }
+        bytes
+    }
+
+    /// Restores a generator from a snapshot produced by `to_snapshot`.
+    ///
+    /// # Parameters
+    ///
+    /// - `snapshot`: The encoded state.
+    ///
+    /// # Returns
+    ///
+    /// Returns the restored generator, or `PrngError::InvalidSnapshot` if the snapshot is truncated,
+    /// has trailing bytes, comes from an unknown format version, was taken from a generator with a
+    /// custom mixer or holds inconsistent values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// # use horizon::prngerror::PrngError;
+    /// let snapshot = Yarrow::new(42).to_snapshot();
+    /// let yarrow_instance = Yarrow::from_snapshot(&snapshot)?;
+    /// assert_eq!(Yarrow::from_snapshot(&snapshot[..4]).err(), Some(PrngError::InvalidSnapshot));
+    /// # Ok::<(), horizon::prngerror::PrngError>(())
+    /// ```
+    pub fn from_snapshot(snapshot: &[u8]) -> Result<Yarrow, PrngError> {
+        let mut input = snapshot;
+        if read_snapshot_bytes(&mut input, 1)? != [SNAPSHOT_VERSION] {
+            return Err(PrngError::InvalidSnapshot);
+        }
+
+        let mut yarrow = Yarrow::new_deterministic(read_snapshot_u64(&mut input)?);
+        yarrow.last_reseed_time = read_snapshot_u64(&mut input)?;
+        yarrow.hash_ops = read_snapshot_u64(&mut input)?;
+        yarrow.entropy_estimate = u32::try_from(read_snapshot_u64(&mut input)?)
+            .ok()
+            .filter(|&estimate| estimate <= POOL_BITS)
+            .ok_or(PrngError::InvalidSnapshot)?;
+        yarrow.rekey_threshold = Some(read_snapshot_u64(&mut input)?).filter(|&threshold| threshold > 0);
+        yarrow.bytes_since_rekey = read_snapshot_u64(&mut input)?;
+        yarrow.rekey_count = read_snapshot_u64(&mut input)?;
+        yarrow.reseed_interval = Some(read_snapshot_u64(&mut input)?).filter(|&interval| interval > 0);
+        yarrow.bytes_since_reseed = read_snapshot_u64(&mut input)?;
+        yarrow.draws = read_snapshot_u64(&mut input)?;
+        let time_counter = read_snapshot_u64(&mut input)?;
+        yarrow.next_pool = usize::try_from(read_snapshot_u64(&mut input)?)
+            .ok()
+            .filter(|&index| index < FORTUNA_POOLS)
+            .ok_or(PrngError::InvalidSnapshot)?;
+        yarrow.pool_reseeds = read_snapshot_u64(&mut input)?;
+        yarrow.bytes_generated = read_snapshot_u64(&mut input)?;
+        yarrow.reseed_count = read_snapshot_u64(&mut input)?;
+        yarrow.time_counter = match read_snapshot_bytes(&mut input, 1)? {
+            [0] => DEFAULT_TIME_COUNTER,
+            [1] => Some(time_counter),
+            _ => return Err(PrngError::InvalidSnapshot),
+        };
+        if read_snapshot_bytes(&mut input, 1)? != [0] {
+            return Err(PrngError::InvalidSnapshot);
+        }
+
+        let pool_len = read_snapshot_len(&mut input)?;
+        yarrow.pool = read_snapshot_bytes(&mut input, pool_len)?.iter().copied().collect();
+        let buffer_len = read_snapshot_len(&mut input)?;
+        yarrow.buffer = read_snapshot_bytes(&mut input, buffer_len)?.to_vec();
+        yarrow.buffer_pos = read_snapshot_len(&mut input)?;
+        for (accumulator, bits) in yarrow.fortuna_pools.iter_mut().zip(&mut yarrow.fortuna_bits) {
+            let accumulator_len = read_snapshot_len(&mut input)?;
+            *accumulator = read_snapshot_bytes(&mut input, accumulator_len)?.to_vec();
+            *bits = read_snapshot_len(&mut input)? as u32;
+        }
+        for pool in [&mut yarrow.fast_pool, &mut yarrow.slow_pool] {
+            let pool_len = read_snapshot_len(&mut input)?;
+            *pool = read_snapshot_bytes(&mut input, pool_len)?.to_vec();
+        }
+        for _ in 0..read_snapshot_len(&mut input)? {
+            let source_id = read_snapshot_bytes(&mut input, 1)?[0];
+            let fast_bits = read_snapshot_len(&mut input)? as u32;
+            let slow_bits = read_snapshot_len(&mut input)? as u32;
+            let to_slow = match read_snapshot_bytes(&mut input, 1)? {
+                [0] => false,
+                [1] => true,
+                _ => return Err(PrngError::InvalidSnapshot),
+            };
+            yarrow.entropy_sources.insert(source_id, SourceEstimate { fast_bits, slow_bits, to_slow });
+        }
+
+        if yarrow.buffer_pos > yarrow.buffer.len() || !input.is_empty() {
+            return Err(PrngError::InvalidSnapshot);
+        }
+        Ok(yarrow)
+    }
+
+    /// Generates a leading digit following Benford's law.
+    ///
+    /// Digit `d` is returned with probability `log10(1 + 1/d)`, so 1 appears about 30% of the time
+    /// and 9 under 5%.
+    ///
+    /// # Returns
+    ///
+    /// Returns a digit between 1 and 9.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let digit = yarrow_instance.generate_benford_leading_digit();
+    /// println!("{}", digit);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn generate_benford_leading_digit(&mut self) -> u8 {
+        (10f64.powf(self.generate_f64()) as u8).clamp(1, 9)
+    }
+
+    /// Generates a number whose digits follow Benford's law.
+    ///
+    /// The number is `10^(magnitude + u)` rounded down, with `u` uniform in `[0, 1)`, which makes its
+    /// leading digit follow Benford's law.
+    ///
+    /// # Parameters
+    ///
+    /// - `magnitude`: The number of digits after the leading one, at most 18.
+    ///
+    /// # Returns
+    ///
+    /// Returns a number between `10^magnitude` and `10^(magnitude + 1) - 1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `magnitude` is greater than 18, as the result would not fit in a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let amount = yarrow_instance.generate_benford_number(4);
+    /// println!("{}", amount);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn generate_benford_number(&mut self, magnitude: u32) -> u64 {
+        assert!(magnitude <= 18, "Magnitude must not exceed 18");
+
+        let low = 10u64.pow(magnitude);
+        let high = low.saturating_mul(10) - 1;
+        (10f64.powf(magnitude as f64 + self.generate_f64()) as u64).clamp(low, high)
+    }
+
+    /// Picks an index with probability proportional to its weight.
+    ///
+    /// The weights are summed into cumulative sums, then a single bounded draw over the total weight
+    /// picks the index as in `choose_weighted_index_prefix`. Use that method directly when the same
+    /// weights are sampled many times, to avoid re-summing them on every call.
+    ///
+    /// # Parameters
+    ///
+    /// - `weights`: The weight of each index; an index of weight zero is never picked.
+    ///
+    /// # Returns
+    ///
+    /// Returns the chosen index, or `None` if `weights` is empty or sums to zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sum of the weights overflows a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let loot = ["common", "rare", "legendary"];
+    /// let index = yarrow_instance.weighted_index(&[90, 9, 1]).unwrap();
+    /// println!("{}", loot[index]);
+    /// ```
+    pub fn weighted_index(&mut self, weights: &[u64]) -> Option<usize> {
+        match self.try_weighted_index(weights) {
+            Ok(index) => Some(index),
+            Err(PrngError::EmptyInput) => None,
+            Err(_) => panic!("Total weight overflows u64"),
+        }
+    }
+
+    /// Picks an index with probability proportional to its weight, without panicking.
+    ///
+    /// Same as `weighted_index`, except that every failure is reported as an error.
+    ///
+    /// # Parameters
+    ///
+    /// - `weights`: The weight of each index; an index of weight zero is never picked.
+    ///
+    /// # Returns
+    ///
+    /// Returns the chosen index, `PrngError::EmptyInput` if `weights` is empty or sums to zero, or
+    /// `PrngError::Overflow` if the sum of the weights overflows a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// # use horizon::prngerror::PrngError;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let index = yarrow_instance.try_weighted_index(&[90, 9, 1])?;
+    /// assert_eq!(yarrow_instance.try_weighted_index(&[]), Err(PrngError::EmptyInput));
+    /// # Ok::<(), horizon::prngerror::PrngError>(())
+    /// ```
+    pub fn try_weighted_index(&mut self, weights: &[u64]) -> Result<usize, PrngError> {
+        let mut cumulative = Vec::with_capacity(weights.len());
+        let mut total = 0u64;
+        for &weight in weights {
+            total = total.checked_add(weight).ok_or(PrngError::Overflow)?;
+            cumulative.push(total);
+        }
+        self.choose_weighted_index_prefix(&cumulative).ok_or(PrngError::EmptyInput)
+    }
+
+    /// Picks an index according to precomputed cumulative weights.
+    ///
+    /// The caller supplies the running sum of the weights, e.g. `[1, 4, 6]` for weights `[1, 3, 2]`.
+    /// The array must be non-decreasing; it is not validated. A single draw is binary-searched in
+    /// the array, so each call costs O(log n) without re-summing the weights.
+    ///
+    /// # Parameters
+    ///
+    /// - `cumulative`: The non-decreasing cumulative sums of the weights.
+    ///
+    /// # Returns
+    ///
+    /// Returns the chosen index, or `None` if `cumulative` is empty or the total weight is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let index = yarrow_instance.choose_weighted_index_prefix(&[1, 4, 6]);
+    /// println!("{:?}", index);
+    /// ```
+    pub fn choose_weighted_index_prefix(&mut self, cumulative: &[u64]) -> Option<usize> {
+        let total = *cumulative.last()?;
+        if total == 0 {
+            return None;
+        }
+
+        let draw = self.generate_bounded_number(0, total - 1);
+        Some(cumulative.partition_point(|&sum| sum <= draw))
+    }
+
+    /// Rolls dice described in standard dice notation, such as `2d6+3`, `1d20-1` or `4d8*2`.
+    ///
+    /// See `DiceExpr::parse` for the accepted syntax.
+    ///
+    /// # Parameters
+    ///
+    /// - `notation`: The dice notation to roll.
+    ///
+    /// # Returns
+    ///
+    /// Returns the total, or `PrngError::InvalidNotation` if the notation is malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let total = yarrow_instance.roll_notation("2d6+1d4-1").unwrap();
+    /// println!("{}", total);
+    /// ```
+    pub fn roll_notation(&mut self, notation: &str) -> Result<i64, PrngError> {
+        dice::roll_notation(self, notation)
+    }
+
+    /// Generates a random alphanumeric token, e.g. a session id or an API key.
+    ///
+    /// Each character is drawn uniformly from the 62 ASCII letters and digits, so a token carries
+    /// about 5.95 bits of entropy per character.
+    ///
+    /// # Parameters
+    ///
+    /// - `len`: The number of characters in the token.
+    ///
+    /// # Returns
+    ///
+    /// Returns the token.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let session_id = yarrow_instance.generate_token(32);
+    /// println!("{}", session_id);
+    /// ```
+    pub fn generate_token(&mut self, len: usize) -> String {
+        self.generate_token_from_alphabet(len, TOKEN_ALPHABET)
+    }
+
+    /// Generates a random token whose characters are drawn from a custom alphabet.
+    ///
+    /// Each character is picked with the unbiased `generate_range`, so every symbol is equally
+    /// likely whatever the alphabet size. Repeated symbols in the alphabet are picked more often.
+    ///
+    /// # Parameters
+    ///
+    /// - `len`: The number of characters in the token.
+    /// - `alphabet`: The ASCII characters to draw from.
+    ///
+    /// # Returns
+    ///
+    /// Returns the token.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alphabet` is empty or contains a non-ASCII byte.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let pin = yarrow_instance.generate_token_from_alphabet(6, b"0123456789");
+    /// println!("{}", pin);
+    /// ```
+    pub fn generate_token_from_alphabet(&mut self, len: usize, alphabet: &[u8]) -> String {
+        assert!(!alphabet.is_empty(), "Alphabet must not be empty");
+        assert!(alphabet.is_ascii(), "Alphabet must only contain ASCII characters");
+
+        (0..len)
+            .map(|_| char::from(alphabet[self.generate_range(0, alphabet.len() - 1)]))
+            .collect()
+    }
+
+    /// Generates a random (version 4) UUID as defined by RFC 4122.
+    ///
+    /// Sixteen bytes are filled with `fill`, then the high nibble of byte 6 is set to the version
+    /// `4` and the two high bits of byte 8 to the variant `10`, leaving 122 random bits.
+    ///
+    /// # Returns
+    ///
+    /// Returns the UUID's 16 bytes, in the order they appear in its textual form.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let uuid = yarrow_instance.generate_uuid_v4();
+    /// assert_eq!(uuid[6] >> 4, 4);
+    /// ```
+    pub fn generate_uuid_v4(&mut self) -> [u8; 16] {
+        let mut uuid = [0u8; 16];
+        self.fill(&mut uuid);
+        uuid[6] = (uuid[6] & 0x0F) | 0x40;
+        uuid[8] = (uuid[8] & 0x3F) | 0x80;
+        uuid
+    }
+
+    /// Generates a random (version 4) UUID in its hyphenated textual form.
+    ///
+    /// # Returns
+    ///
+    /// Returns the UUID as 32 lowercase hexadecimal digits grouped 8-4-4-4-12, e.g.
+    /// `"f47ac10b-58cc-4372-a567-0e02b2c3d479"`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prng::Yarrow;
+    /// let mut yarrow_instance = Yarrow::new(42);
+    /// let uuid = yarrow_instance.generate_uuid_v4_string();
+    /// println!("{}", uuid);
+    /// ```
+    pub fn generate_uuid_v4_string(&mut self) -> String {
+        let mut text = String::with_capacity(36);
+        for (i, byte) in self.generate_uuid_v4().iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                text.push('-');
+            }
+            text.extend(char::from_digit(u32::from(byte >> 4), 16));
+            text.extend(char::from_digit(u32::from(byte & 0x0F), 16));
+        }
+        text
+    }
+}
+
+/// Configures and creates a `Yarrow` generator.
+///
+/// Like `Yarrow::new_deterministic`, the built generator's stream depends only on its configuration.
+///
+/// # Fields
+///
+/// - `seed`: The initial seed for the generator.
+/// - `clock`: The `Clock` to use, or `None` for a time counter starting at zero, as with `Yarrow::new_deterministic`.
+/// - `rekey_threshold`: The number of generated bytes after which the generator rekeys, or `None` to never rekey.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::prng::YarrowBuilder;
+/// let mut yarrow_instance = YarrowBuilder::new(42)
+///     .rekey_threshold(1 << 20)
+///     .build();
+/// let random_bytes = yarrow_instance.generate_random_bytes(16);
+/// ```
+pub struct YarrowBuilder {
+    seed: u64,
+    clock: Option<Box<dyn Clock + Send>>,
+    rekey_threshold: Option<u64>,
+}
+
+impl YarrowBuilder {
+    /// Creates a builder for a generator with the specified seed and the default configuration.
+    ///
+    /// # Parameters
+    ///
+    /// - `seed`: A 64-bit unsigned integer serving as the initial seed for the generator.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `YarrowBuilder`.
+    pub fn new(seed: u64) -> Self {
+        YarrowBuilder { seed, clock: None, rekey_threshold: None }
+    }
+
+    /// Sets the `Clock` that drives the reseed window.
+    ///
+    /// # Parameters
+    ///
+    /// - `clock`: The clock read in place of a time counter starting at zero.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated builder.
+    pub fn clock(mut self, clock: Box<dyn Clock + Send>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Sets the number of generated bytes after which the generator rekeys its internal state.
+    ///
+    /// The check runs at the end of each generation call, so a single call may exceed the threshold by
+    /// up to its own length before the rekey happens.
+    ///
+    /// # Parameters
+    ///
+    /// - `bytes`: The rekey threshold in bytes.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated builder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is zero.
+    pub fn rekey_threshold(mut self, bytes: u64) -> Self {
+        assert!(bytes > 0, "Rekey threshold must be greater than zero");
+        self.rekey_threshold = Some(bytes);
+        self
+    }
+
+    /// Creates the configured generator.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `Yarrow` instance.
+    pub fn build(self) -> Yarrow {
+        let mut yarrow = Yarrow::with_mixer(self.seed, Box::new(Sha3Mixer));
+        match self.clock {
+            Some(clock) => {
+                yarrow.time_counter = None;
+                yarrow.clock = Some(clock);
+            }
+            None => yarrow.time_counter = Some(0),
+        }
+        yarrow.rekey_threshold = self.rekey_threshold;
+        yarrow
+    }
+}
+
+/// Random bytes returned by `Yarrow::generate_small`, stored inline when they fit in `INLINE_BYTES`.
+///
+/// # Variants
+///
+/// - `Inline`: The first `len` bytes of `bytes` hold the output.
+/// - `Heap`: The output, too large to be stored inline.
+#[derive(Debug, Clone)]
+pub enum SmallBytes {
+    Inline { bytes: [u8; INLINE_BYTES], len: usize },
+    Heap(Vec<u8>),
+}
+
+impl SmallBytes {
+    /// Returns whether the bytes are stored inline.
+    pub fn is_inline(&self) -> bool {
+        matches!(self, SmallBytes::Inline { .. })
+    }
+}
+
+impl core::ops::Deref for SmallBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            SmallBytes::Inline { bytes, len } => &bytes[..*len],
+            SmallBytes::Heap(bytes) => bytes,
+        }
+    }
+}
+
+/// The blocks making up a single output, consumed byte by byte.
+///
+/// Bytes are served from the current block until it runs out, and only then is the next block
+/// computed, so every byte of each mixer call ends up in the output.
+///
+/// # Fields
+///
+/// - `counter`: The index of the next block to compute.
+/// - `block`: The current block.
+/// - `pos`: The position of the next byte to serve from `block`.
+struct OutputBlocks {
+    counter: u64,
+    block: Vec<u8>,
+    pos: usize,
+}
+
+impl OutputBlocks {
+    /// Takes the next `count` bytes of the output.
+    ///
+    /// # Parameters
+    ///
+    /// - `yarrow`: The generator whose pool keys the blocks.
+    /// - `count`: The number of bytes to take.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of exactly `count` bytes.
+    fn take(&mut self, yarrow: &mut Yarrow, count: usize) -> Vec<u8> {
+        let mut random_bytes = vec![0u8; count];
+        self.fill(yarrow, &mut random_bytes);
+        random_bytes
+    }
+
+    /// Fills `dest` with the next bytes of the output.
+    ///
+    /// # Parameters
+    ///
+    /// - `yarrow`: The generator whose pool keys the blocks.
+    /// - `dest`: The buffer to fill.
+    fn fill(&mut self, yarrow: &mut Yarrow, dest: &mut [u8]) {
+        let mut filled = 0;
+
+        while filled < dest.len() {
+            if self.pos == self.block.len() {
+                self.block = yarrow.output_block(self.counter);
+                self.counter += 1;
+                self.pos = 0;
+            }
+            let available = (self.block.len() - self.pos).min(dest.len() - filled);
+            dest[filled..filled + available].copy_from_slice(&self.block[self.pos..self.pos + available]);
+            self.pos += available;
+            filled += available;
+        }
+        yarrow.bytes_since_rekey += dest.len() as u64;
+        yarrow.bytes_since_reseed += dest.len() as u64;
+        yarrow.bytes_generated += dest.len() as u64;
+    }
+}
+
+/// An endless iterator over random bytes, returned by `Yarrow::iter_bytes`.
+pub struct ByteIter<'a> {
+    yarrow: &'a mut Yarrow,
+}
+
+impl Iterator for ByteIter<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        Some(self.yarrow.next_byte())
+    }
+}
+
+/// An endless iterator over random 64-bit unsigned integers, returned by `Yarrow::iter_u64`.
+pub struct U64Iter<'a> {
+    yarrow: &'a mut Yarrow,
+}
+
+impl Iterator for U64Iter<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        Some(self.yarrow.generate_random_number())
+    }
+}
+
+/// A `RandomSource` producing at most a fixed number of bytes, returned by `Yarrow::bounded`.
+///
+/// A request larger than the remaining budget fails with `PrngError::Exhausted` without consuming
+/// any of it, so smaller requests can still succeed afterwards.
+///
+/// # Fields
+///
+/// - `yarrow`: The borrowed generator producing the bytes.
+/// - `remaining`: The number of bytes that may still be produced.
+pub struct BoundedYarrow<'a> {
+    yarrow: &'a mut Yarrow,
+    remaining: usize,
+}
+
+impl BoundedYarrow<'_> {
+    /// Returns the number of bytes that may still be produced.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl RandomSource for BoundedYarrow<'_> {
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), PrngError> {
+        if dest.len() > self.remaining {
+            return Err(PrngError::Exhausted);
+        }
+
+        self.yarrow.try_fill_bytes(dest)?;
+        self.remaining -= dest.len();
+        Ok(())
+    }
+}
+
+impl Yarrow {
+    /// Overwrites the seed, the pool and the buffered output with zeros.
+    ///
+    /// The writes are volatile and followed by a compiler fence, so they are not optimized away even
+    /// though the generator is never read again. Only the current allocations are wiped: copies left
+    /// behind when the pool or the buffer were reallocated are out of reach.
+    fn wipe(&mut self) {
+        wipe_bytes(self.pool.make_contiguous());
+        wipe_bytes(&mut self.buffer);
+        for accumulator in &mut self.fortuna_pools {
+            wipe_bytes(accumulator);
+        }
+        self.fortuna_bits.fill(0);
+        wipe_bytes(&mut self.fast_pool);
+        wipe_bytes(&mut self.slow_pool);
+        // SAFETY: `self.seed` is a valid, aligned `u64` borrowed mutably.
+        unsafe { ptr::write_volatile(&mut self.seed, 0) };
+        atomic::compiler_fence(Ordering::SeqCst);
+    }
+}
+
+/// Formats the generator's public metadata only.
+///
+/// The seed, the pool, the Fortuna accumulators and the buffered output are secret, so only their
+/// sizes are shown; printing a generator into a log never reveals its state.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::prng::Yarrow;
+/// let yarrow_instance = Yarrow::new(42);
+/// println!("{:?}", yarrow_instance);
+/// ```
+impl fmt::Debug for Yarrow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Yarrow")
+            .field("pool_len", &self.pool.len())
+            .field("pending_entropy_pools", &self.fortuna_pools.iter().filter(|accumulator| !accumulator.is_empty()).count())
+            .field("pending_entropy_bits", &self.pending_bits())
+            .field("entropy_sources", &self.entropy_sources.len())
+            .field("buffered_bytes", &(self.buffer.len() - self.buffer_pos))
+            .field("last_reseed_time", &self.last_reseed_time)
+            .field("entropy_estimate", &self.entropy_estimate)
+            .field("hash_ops", &self.hash_ops)
+            .field("draws", &self.draws)
+            .field("rekey_count", &self.rekey_count)
+            .field("bytes_generated", &self.bytes_generated)
+            .field("reseed_count", &self.reseed_count)
+            .field("scripted", &self.script.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Creates a generator seeded from OS entropy.
+///
+/// The result is non-deterministic by design: two default generators never produce the same
+/// sequence. If the operating system cannot provide random bytes, the seed falls back to the current
+/// time in nanoseconds. Use `Yarrow::new` or `Yarrow::new_deterministic` for reproducible output.
+/// Not available with the `deterministic-only` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::prng::Yarrow;
+/// let mut yarrow_instance = Yarrow::default();
+/// let random_bytes = yarrow_instance.generate_random_bytes(16);
+/// ```
+#[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+impl Default for Yarrow {
+    fn default() -> Self {
+        Yarrow::from_os_entropy().unwrap_or_else(|_| {
+            let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_nanos() as u64);
+            Yarrow::new(nanos)
+        })
+    }
+}
+
+/// Wipes the generator's secrets when it is dropped, see `wipe`.
+impl Drop for Yarrow {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+impl RandomSource for Yarrow {
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), PrngError> {
+        if let Some(script) = self.script.as_mut() {
+            return script.try_fill_bytes(dest);
+        }
+
+        self.fill(dest);
+        Ok(())
+    }
+}
+
+impl HorizonRng for Yarrow {
+    fn reseed(&mut self, seed: &[u8]) {
+        self.reseed_bytes(seed);
+    }
+}
+
+/// A pool of `Yarrow` generators shared between threads, one shard per CPU.
+///
+/// Each request is routed to a shard chosen from the calling thread's id, so threads mostly lock
+/// different generators instead of contending on a single one. Every `CROSS_RESEED_INTERVAL`
+/// requests, the shard serving the request is reseeded from a master generator, keeping the shards
+/// independent while still refreshing them from a common source.
+///
+/// # Fields
+///
+/// - `master`: The generator used to seed and periodically reseed the shards.
+/// - `shards`: The generators serving requests.
+/// - `requests`: The number of requests served so far, wrapping around on overflow. An `AtomicUsize`
+///   rather than an `AtomicU64`, so that targets without 64-bit atomics can build it.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::prng::ShardedYarrow;
+/// let sharded = ShardedYarrow::new(42);
+/// let random_number = sharded.generate_random_number();
+/// println!("{}", random_number);
+/// ```
+#[cfg(feature = "std")]
+pub struct ShardedYarrow {
+    master: Mutex<Yarrow>,
+    shards: Vec<Mutex<Yarrow>>,
+    requests: AtomicUsize,
+}
+
+#[cfg(feature = "std")]
+impl ShardedYarrow {
+    /// Creates a new `ShardedYarrow` with one shard per available CPU.
+    ///
+    /// # Parameters
+    ///
+    /// - `seed`: A 64-bit unsigned integer serving as the seed for the master generator.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `ShardedYarrow` instance.
+    pub fn new(seed: u64) -> Self {
+        let shard_count = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        ShardedYarrow::with_shards(seed, shard_count)
+    }
+
+    /// Creates a new `ShardedYarrow` with the given number of shards.
+    ///
+    /// # Parameters
+    ///
+    /// - `seed`: A 64-bit unsigned integer serving as the seed for the master generator.
+    /// - `shard_count`: The number of shards, at least one shard is always created.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `ShardedYarrow` instance.
+    pub fn with_shards(seed: u64, shard_count: usize) -> Self {
+        let mut master = Yarrow::new(seed);
+        let shards = (0..shard_count.max(1))
+            .map(|_| Mutex::new(Yarrow::new(master.generate_random_number())))
+            .collect();
+
+        ShardedYarrow {
+            master: Mutex::new(master),
+            shards,
+            requests: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of shards.
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Runs `f` on the shard assigned to the calling thread, reseeding it from the master when due.
+    fn with_shard<T>(&self, f: impl FnOnce(&mut Yarrow) -> T) -> T {
+        let mut hasher = DefaultHasher::new();
+        std::thread::current().id().hash(&mut hasher);
+        let index = (hasher.finish() % self.shards.len() as u64) as usize;
+
+        let mut shard = self.shards[index].lock().unwrap();
+        if self.requests.fetch_add(1, Ordering::Relaxed) % CROSS_RESEED_INTERVAL == CROSS_RESEED_INTERVAL - 1 {
+            let new_seed = self.master.lock().unwrap().generate_random_number();
+            shard.reseed(new_seed);
+        }
+
+        f(&mut shard)
+    }
+
+    /// Generates a sequence of random bytes from the calling thread's shard.
+    ///
+    /// # Parameters
+    ///
+    /// - `count`: The number of random bytes to generate.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of unsigned 8-bit integers representing the generated random bytes.
+    pub fn generate_random_bytes(&self, count: usize) -> Vec<u8> {
+        self.with_shard(|yarrow| yarrow.generate_random_bytes(count))
+    }
+
+    /// Generates a random 64-bit unsigned integer from the calling thread's shard.
+    ///
+    /// # Returns
+    ///
+    /// Returns a 64-bit unsigned integer representing the generated random number.
+    pub fn generate_random_number(&self) -> u64 {
+        self.with_shard(|yarrow| yarrow.generate_random_number())
+    }
+}
+
+/// A generator shared between threads whose output depends only on the seed and a sequence number.
+///
+/// Each request is assigned the next value of a monotonic sequence counter, and its output is
+/// generated by a fresh `Yarrow` seeded from the seed with the sequence number absorbed as a label.
+/// No generator state is shared between requests, so the output for a given sequence number is the
+/// same however concurrent requests interleave, which makes concurrent simulations reproducible.
+/// Like `Yarrow::new_deterministic`, it must not be used where the output has to stay secret.
+///
+/// # Fields
+///
+/// - `seed`: The seed every request's output is derived from.
+/// - `next_sequence`: The sequence number assigned to the next request.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::prng::SequencedYarrow;
+/// let sequenced = SequencedYarrow::new(42);
+/// let (sequence, random_bytes) = sequenced.generate_random_bytes(16);
+/// assert_eq!(random_bytes, sequenced.generate_at(sequence, 16));
+/// ```
+#[cfg(target_has_atomic = "64")]
+pub struct SequencedYarrow {
+    seed: u64,
+    next_sequence: AtomicU64,
+}
+
+#[cfg(target_has_atomic = "64")]
+impl SequencedYarrow {
+    /// Creates a new `SequencedYarrow` starting at sequence number 0.
+    ///
+    /// # Parameters
+    ///
+    /// - `seed`: A 64-bit unsigned integer serving as the seed for every request.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `SequencedYarrow` instance.
+    pub fn new(seed: u64) -> Self {
+        SequencedYarrow { seed, next_sequence: AtomicU64::new(0) }
+    }
+
+    /// Generates random bytes for the next sequence number.
+    ///
+    /// # Parameters
+    ///
+    /// - `count`: The number of random bytes to generate.
+    ///
+    /// # Returns
+    ///
+    /// Returns the sequence number assigned to the request, and the generated bytes.
+    pub fn generate_random_bytes(&self, count: usize) -> (u64, Vec<u8>) {
+        let sequence = self.next_sequence.fetch_add(1, Ordering::Relaxed);
+        (sequence, self.generate_at(sequence, count))
+    }
+
+    /// Generates the random bytes of a given sequence number, without advancing the counter.
+    ///
+    /// # Parameters
+    ///
+    /// - `sequence`: The sequence number whose output to generate.
+    /// - `count`: The number of random bytes to generate.
+    ///
+    /// # Returns
+    ///
+    /// Returns the generated bytes.
+    pub fn generate_at(&self, sequence: u64, count: usize) -> Vec<u8> {
+        let mut yarrow = Yarrow::new_deterministic(self.seed);
+        yarrow.absorb(sequence);
+        yarrow.generate_random_bytes(count)
+    }
+}
+
+/// A cloneable handle to a `Yarrow` that can be drawn from through a shared reference.
+///
+/// Every clone shares the same generator behind an `Arc<Mutex<_>>`, and every call locks it for the
+/// duration of the draw. This is simple and keeps a single stream, but threads drawing through the
+/// same handle serialize on the lock; `thread_yarrow` avoids the contention by giving each thread
+/// its own generator, and `ShardedYarrow` by spreading threads over several.
+///
+/// # Fields
+///
+/// - `yarrow`: The shared generator.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::prng::{ThreadYarrow, Yarrow};
+/// let shared = ThreadYarrow::new(Yarrow::new(42));
+/// let handle = shared.clone();
+/// let worker = std::thread::spawn(move || handle.next_u64());
+/// println!("{} {}", shared.next_u64(), worker.join().unwrap());
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub struct ThreadYarrow {
+    yarrow: Arc<Mutex<Yarrow>>,
+}
+
+#[cfg(feature = "std")]
+impl ThreadYarrow {
+    /// Creates a new `ThreadYarrow` sharing the given generator.
+    ///
+    /// # Parameters
+    ///
+    /// - `yarrow`: The generator to share.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `ThreadYarrow` instance.
+    pub fn new(yarrow: Yarrow) -> Self {
+        ThreadYarrow { yarrow: Arc::new(Mutex::new(yarrow)) }
+    }
+
+    /// Generates a random 64-bit unsigned integer from the shared generator.
+    ///
+    /// # Returns
+    ///
+    /// Returns a 64-bit unsigned integer representing the generated random number.
+    pub fn next_u64(&self) -> u64 {
+        self.yarrow.lock().unwrap().generate_random_number()
+    }
+
+    /// Fills a caller-provided buffer with random bytes from the shared generator.
+    ///
+    /// # Parameters
+    ///
+    /// - `buf`: The buffer to fill.
+    pub fn fill(&self, buf: &mut [u8]) {
+        self.yarrow.lock().unwrap().fill(buf);
+    }
+
+    /// Generates a random 64-bit unsigned integer within a specified range from the shared generator.
+    ///
+    /// # Parameters
+    ///
+    /// - `min`: The minimum value of the generated number (inclusive).
+    /// - `max`: The maximum value of the generated number (inclusive).
+    ///
+    /// # Returns
+    ///
+    /// Returns a 64-bit unsigned integer within the specified range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    pub fn generate_bounded_number(&self, min: u64, max: u64) -> u64 {
+        self.yarrow.lock().unwrap().generate_bounded_number(min, max)
+    }
+}
+
+#[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+thread_local! {
+    /// The calling thread's generator, created on first use by `thread_yarrow`.
+    static THREAD_YARROW: ThreadYarrow =
+        ThreadYarrow::new(Yarrow::from_os_entropy().expect("OS entropy unavailable"));
+}
+
+/// Returns a handle to the calling thread's own generator, seeded from OS entropy on first use.
+///
+/// Each thread gets a distinct generator, so the lock is never contended unless the handle is sent
+/// to another thread. Not available with the `deterministic-only` feature, since the seed cannot
+/// be reproduced.
+///
+/// # Returns
+///
+/// Returns a handle to the calling thread's generator.
+///
+/// # Panics
+///
+/// Panics on first use in a thread if the operating system cannot provide random bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::prng::thread_yarrow;
+/// let mut token = [0u8; 16];
+/// thread_yarrow().fill(&mut token);
+/// let die = thread_yarrow().generate_bounded_number(1, 6);
+/// ```
+#[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+pub fn thread_yarrow() -> ThreadYarrow {
+    THREAD_YARROW.with(ThreadYarrow::clone)
+}
+
+/// Draws a random value from the calling thread's generator, like `rand::random`.
+///
+/// The generator is the one returned by `thread_yarrow`, seeded from OS entropy on first use, so the
+/// values are not reproducible. Not available with the `deterministic-only` feature.
+///
+/// # Returns
+///
+/// Returns a value drawn uniformly from the whole domain of `T`, or from `[0, 1)` for floats.
+///
+/// # Panics
+///
+/// Panics on first use in a thread if the operating system cannot provide random bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::prng::random;
+/// let id: u64 = random();
+/// let heads = random::<bool>();
+/// let ratio = random::<f64>();
+/// assert!((0.0..1.0).contains(&ratio));
+/// ```
+#[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+pub fn random<T: RandomValue>() -> T {
+    THREAD_YARROW.with(|handle| T::random_from(&mut handle.yarrow.lock().unwrap()))
+}
+
+/// A `Yarrow` shared between tenants, each limited to a number of random bytes per time window.
+///
+/// # Fields
+///
+/// - `yarrow`: The generator serving every tenant.
+/// - `limit`: The number of bytes each tenant may draw per window.
+/// - `window`: The length of a quota window.
+/// - `usage`: For each tenant, the start of its current window and the bytes drawn in it.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::prng::{QuotaYarrow, Yarrow};
+/// # use std::time::Duration;
+/// let mut quota = QuotaYarrow::new(Yarrow::new(42), 1024, Duration::from_secs(60));
+/// let random_bytes = quota.generate_random_bytes(&"tenant-a", 16);
+/// println!("{:?}", random_bytes);
+/// ```
+#[cfg(feature = "std")]
+pub struct QuotaYarrow<K> {
+    yarrow: Yarrow,
+    limit: usize,
+    window: Duration,
+    usage: HashMap<K, (Instant, usize)>,
+}
+
+#[cfg(feature = "std")]
+impl<K: Hash + Eq + Clone> QuotaYarrow<K> {
+    /// Creates a new `QuotaYarrow` around a generator.
+    ///
+    /// # Parameters
+    ///
+    /// - `yarrow`: The generator serving every tenant.
+    /// - `limit`: The number of bytes each tenant may draw per window.
+    /// - `window`: The length of a quota window.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `QuotaYarrow` with no usage recorded.
+    pub fn new(yarrow: Yarrow, limit: usize, window: Duration) -> Self {
+        QuotaYarrow { yarrow, limit, window, usage: HashMap::new() }
+    }
+
+    /// Returns the number of bytes `tenant` may still draw in its current window.
+    pub fn remaining(&self, tenant: &K) -> usize {
+        match self.usage.get(tenant) {
+            Some(&(start, used)) if start.elapsed() < self.window => self.limit.saturating_sub(used),
+            _ => self.limit,
+        }
+    }
+
+    /// Generates random bytes on behalf of `tenant`, charging them to its quota.
+    ///
+    /// # Parameters
+    ///
+    /// - `tenant`: The tenant drawing the bytes.
+    /// - `count`: The number of random bytes to generate.
+    ///
+    /// # Returns
+    ///
+    /// Returns the generated bytes, or `PrngError::QuotaExceeded` if `count` exceeds what the
+    /// tenant has left in its window. A refused request is not charged.
+    pub fn generate_random_bytes(&mut self, tenant: &K, count: usize) -> Result<Vec<u8>, PrngError> {
+        if count > self.remaining(tenant) {
+            return Err(PrngError::QuotaExceeded);
+        }
+
+        let now = Instant::now();
+        let window = self.window;
+        let entry = self.usage.entry(tenant.clone()).or_insert((now, 0));
+        if now.duration_since(entry.0) >= window {
+            *entry = (now, 0);
+        }
+        entry.1 += count;
+
+        Ok(self.yarrow.generate_random_bytes(count))
+    }
+
+    /// Resets the quota of `tenant`, giving it a full allowance again.
+    pub fn reset_quota(&mut self, tenant: &K) {
+        self.usage.remove(tenant);
+    }
+
+    /// Resets the quotas of every tenant.
+    pub fn reset_all_quotas(&mut self) {
+        self.usage.clear();
+    }
+}
+
+/// Tracks the differential-privacy budget spent by noise draws.
+///
+/// Budgets compose by simple summation of each draw's epsilon (basic sequential composition).
+///
+/// # Fields
+///
+/// - `spent`: The cumulative epsilon spent so far.
+/// - `draws`: The number of noise draws recorded.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::prng::{PrivacyAccountant, Yarrow};
+/// let mut yarrow_instance = Yarrow::new(42);
+/// let mut accountant = PrivacyAccountant::new();
+/// let noisy_count = 120.0 + accountant.laplace(&mut yarrow_instance, 1.0, 0.5);
+/// println!("{} (epsilon spent: {})", noisy_count, accountant.budget_spent());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PrivacyAccountant {
+    spent: f64,
+    draws: u64,
+}
+
+impl PrivacyAccountant {
+    /// Creates an accountant with no budget spent.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `PrivacyAccountant`.
+    pub fn new() -> Self {
+        PrivacyAccountant::default()
+    }
+
+    /// Draws Laplace noise calibrated for `sensitivity` and `epsilon`, and records `epsilon` as spent.
+    ///
+    /// # Parameters
+    ///
+    /// - `yarrow`: The generator to draw from.
+    /// - `sensitivity`: The L1 sensitivity of the query being protected.
+    /// - `epsilon`: The privacy budget consumed by this draw.
+    ///
+    /// # Returns
+    ///
+    /// Returns noise drawn with scale `sensitivity / epsilon`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sensitivity` or `epsilon` is not strictly positive.
+    #[cfg(feature = "std")]
+    pub fn laplace(&mut self, yarrow: &mut Yarrow, sensitivity: f64, epsilon: f64) -> f64 {
+        assert!(sensitivity > 0.0, "Sensitivity must be greater than zero");
+        assert!(epsilon > 0.0, "Epsilon must be greater than zero");
+
+        let noise = yarrow.generate_laplace(sensitivity / epsilon);
+        self.record(epsilon);
+        noise
+    }
+
+    /// Records a noise draw consuming `epsilon`, for draws made outside the accountant.
+    ///
+    /// # Parameters
+    ///
+    /// - `epsilon`: The privacy budget consumed by the draw.
+    pub fn record(&mut self, epsilon: f64) {
+        self.spent += epsilon;
+        self.draws += 1;
+    }
+
+    /// Returns the cumulative epsilon spent so far.
+    pub fn budget_spent(&self) -> f64 {
+        self.spent
+    }
+
+    /// Returns the number of noise draws recorded.
+    pub fn draws(&self) -> u64 {
+        self.draws
+    }
+}
+
+/// Exposes `Yarrow` to code written against `rand_core`.
+#[cfg(feature = "rand-compat")]
+impl rand_core::RngCore for Yarrow {
+    fn next_u32(&mut self) -> u32 {
+        (self.generate_random_number() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.generate_random_number()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.fill(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        rand_core::RngCore::fill_bytes(self, dest);
+        Ok(())
+    }
+}
+
+/// Marks `Yarrow` as suitable for cryptographic use.
+///
+/// Every output block is a SHA3-512 hash of the entropy pool, which is rehashed after each output
+/// and never exposed, so observed output does not reveal past or future output. The claim only
+/// holds for a generator seeded with enough entropy: a guessable seed passed to `new` makes the
+/// whole stream guessable.
+#[cfg(feature = "rand-compat")]
+impl rand_core::CryptoRng for Yarrow {}
+
+/// Creates a reproducible `Yarrow` from a 32-byte seed, for code written against `SeedableRng`.
+///
+/// The pool is the SHA3-512 hash of a domain label and the seed, and the generator runs on the time
+/// counter like `new_deterministic`, so the same seed always gives the same stream. The seed is
+/// not credited as entropy: use `from_os_entropy` for a generator seeded by the operating system.
+#[cfg(feature = "rand-compat")]
+impl rand_core::SeedableRng for Yarrow {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut hasher = Sha3_512::new();
+        hasher.update(b"horizon-yarrow-seedable");
+        hasher.update(seed);
+        let state = hasher.finalize();
+
+        let mut yarrow = Yarrow::new_deterministic(0);
+        yarrow.set_state(&state);
+        yarrow.hash_ops += 1;
+        yarrow
+    }
+}
+
+/// Serializes `Yarrow` as its `to_snapshot` encoding, so a paused simulation resumes the same stream.
+///
+/// Human-readable formats such as JSON receive the snapshot as a hex string and binary formats as a
+/// byte string, so the pool stays compact in both. The limits of `to_snapshot` apply: the mixer, a
+/// custom clock and an event sink are not captured.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Yarrow {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut snapshot = self.to_snapshot();
+        let result = if serializer.is_human_readable() {
+            serializer.serialize_str(&hex::encode(&snapshot))
+        } else {
+            serializer.serialize_bytes(&snapshot)
+        };
+        wipe_bytes(&mut snapshot);
+        result
+    }
+}
+
+/// Restores `Yarrow` from the encoding written by its `Serialize` implementation, see `from_snapshot`.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Yarrow {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(SnapshotVisitor)
+        } else {
+            deserializer.deserialize_bytes(SnapshotVisitor)
+        }
+    }
+}
+
+/// Decodes a serialized snapshot, given as a hex string, a byte string or a sequence of bytes.
+#[cfg(feature = "serde")]
+struct SnapshotVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for SnapshotVisitor {
+    type Value = Yarrow;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a Yarrow snapshot, as bytes or a hex string")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Yarrow, E> {
+        let mut snapshot = hex::decode(value).map_err(|_| E::custom(PrngError::InvalidSnapshot))?;
+        let yarrow = Yarrow::from_snapshot(&snapshot).map_err(E::custom);
+        wipe_bytes(&mut snapshot);
+        yarrow
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, value: &[u8]) -> Result<Yarrow, E> {
+        Yarrow::from_snapshot(value).map_err(E::custom)
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Yarrow, A::Error> {
+        let mut snapshot = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            snapshot.push(byte);
+        }
+        let yarrow = Yarrow::from_snapshot(&snapshot).map_err(serde::de::Error::custom);
+        wipe_bytes(&mut snapshot);
+        yarrow
+    }
+}
+
+/// Overwrites a buffer with zeros through volatile writes, so the compiler cannot elide them.
+///
+/// # Parameters
+///
+/// - `bytes`: The buffer to wipe.
+pub(crate) fn wipe_bytes(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        // SAFETY: `byte` is a valid, aligned `u8` borrowed mutably.
+        unsafe { ptr::write_volatile(byte, 0) };
+    }
+    atomic::compiler_fence(Ordering::SeqCst);
+}
+
+/// Splits the next `len` bytes off a snapshot being decoded.
+///
+/// # Parameters
+///
+/// - `input`: The undecoded rest of the snapshot, advanced past the returned bytes.
+/// - `len`: The number of bytes to read.
+///
+/// # Returns
+///
+/// Returns the bytes, or `PrngError::InvalidSnapshot` if fewer than `len` bytes remain.
+fn read_snapshot_bytes<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], PrngError> {
+    if input.len() < len {
+        return Err(PrngError::InvalidSnapshot);
+    }
+    let (bytes, rest) = input.split_at(len);
+    *input = rest;
+    Ok(bytes)
+}
+
+/// Reads a big-endian 64-bit integer from a snapshot being decoded.
+fn read_snapshot_u64(input: &mut &[u8]) -> Result<u64, PrngError> {
+    let mut word = [0u8; 8];
+    word.copy_from_slice(read_snapshot_bytes(input, 8)?);
+    Ok(u64::from_be_bytes(word))
+}
+
+/// Reads a big-endian 32-bit length from a snapshot being decoded.
+fn read_snapshot_len(input: &mut &[u8]) -> Result<usize, PrngError> {
+    let mut word = [0u8; 4];
+    word.copy_from_slice(read_snapshot_bytes(input, 4)?);
+    Ok(u32::from_be_bytes(word) as usize)
+}
+
+/// Converts a color from HSL to RGB.
+///
+/// # Parameters
+///
+/// - `hue`: The hue, as a fraction of the color wheel in `[0, 1)`.
+/// - `saturation`: The saturation in `[0, 1]`.
+/// - `lightness`: The lightness in `[0, 1]`.
+///
+/// # Returns
+///
+/// Returns the color as a `(red, green, blue)` tuple.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let red = hsl_to_rgb(0.0, 1.0, 0.5);
+/// assert_eq!(red, (255, 0, 0));
+/// ```
+#[cfg(feature = "std")]
+fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> (u8, u8, u8) {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue * 6.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let to_byte = |channel: f64| ((channel + m) * 255.0).round() as u8;
+
+    (to_byte(r), to_byte(g), to_byte(b))
+}
+
+/// Shuffles the elements of a mutable slice using the Fisher-Yates algorithm with any generator.
+///
+/// The swap indices are drawn from `rng` with `HorizonRng::generate_bounded_number`, in the same
+/// order as `Yarrow::shuffle`, so a `Yarrow` permutes the slice exactly as its own method does and
+/// a generator in a given state always produces the same permutation.
+///
+/// # Parameters
+///
+/// - `items`: A mutable slice of elements to be shuffled.
+/// - `rng`: The generator the swap indices are drawn from.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::prng::{Yarrow, shuffle};
+/// let mut yarrow_instance = Yarrow::new(42);
+/// let mut elements = vec![1, 2, 3, 4, 5];
+/// shuffle(&mut elements, &mut yarrow_instance);
+/// println!("{:?}", elements);
+/// ```
+pub fn shuffle<T, R: HorizonRng + ?Sized>(items: &mut [T], rng: &mut R) {
+    for i in (1..items.len()).rev() {
+        let j = rng.generate_bounded_number(0, i as u64) as usize;
+        items.swap(i, j);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use super::*;
+
+    /// The permutation of `0..10` produced by `Yarrow::new_deterministic(42).shuffle` with the clock fixed.
+    const PINNED_SHUFFLE: [u32; 10] = [7, 5, 3, 6, 1, 0, 4, 8, 2, 9];
+
+    /// The first Q16.16 values produced by `Yarrow::new_deterministic(42)` with the clock fixed.
+    const PINNED_FIXED_Q16_16: [i32; 4] = [54813, 25289, 48648, 32961];
+
+    #[derive(Clone)]
+    struct MockClock {
+        secs: Arc<AtomicU64>,
+    }
+
+    impl Clock for MockClock {
+        fn now_secs(&self) -> u64 {
+            self.secs.load(Ordering::SeqCst)
+        }
+    }
+
+    #[derive(Clone)]
+    struct StuckMixer;
+
+    impl Mixer for StuckMixer {
+        fn mix(&mut self, _pool: &[u8], _input: &[u8]) -> Vec<u8> {
+            vec![0xA5; 64]
+        }
+    }
+
+    #[derive(Clone)]
+    struct CountingMixer {
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl Mixer for CountingMixer {
+        fn mix(&mut self, pool: &[u8], input: &[u8]) -> Vec<u8> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            pool.iter().chain(input.iter()).rev().take(64).map(|b| b.wrapping_add(1)).collect()
+        }
+    }
+
+    #[test]
+    fn test_add_entropy() {
+        let mut rng = Yarrow::new(12345);
+        let initial_state = rng.fortuna_pools.clone();
+        rng.add_entropy(67890);
+        assert_ne!(rng.fortuna_pools, initial_state, "L'ajout d'entropie n'a pas modifié l'état du générateur");
+    }
+
+    #[test]
+    fn test_reseed() {
+        let mut rng = Yarrow::new(12345);
+        let initial_state = rng.pool.clone();
+        rng.reseed(67890);
+        assert_ne!(rng.pool, initial_state, "La méthode reseed n'a pas modifié l'état du générateur");
+    }
+
+    #[test]
+    fn test_generate_random_bytes() {
+        let mut rng = Yarrow::new(12345);
+        let first = rng.generate_random_bytes(10);
+        let second = rng.generate_random_bytes(10);
+        assert_ne!(first, second, "Les deux appels à generate_random_bytes ont produit les mêmes résultats");
+    }
+
+    #[test]
+    fn test_custom_mixer() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut rng = Yarrow::with_mixer(12345, Box::new(CountingMixer { calls: Arc::clone(&calls) }));
+        let mut same = Yarrow::with_mixer(12345, Box::new(CountingMixer { calls: Arc::new(AtomicUsize::new(0)) }));
+        let mut default = Yarrow::new_deterministic(12345);
+
+        let first = rng.generate_random_bytes(16);
+        assert!(calls.load(Ordering::SeqCst) > 0, "Le mélangeur personnalisé n'a pas été utilisé");
+        assert_eq!(first, same.generate_random_bytes(16), "Le mélangeur personnalisé n'est pas déterministe");
+        assert_ne!(first, default.generate_random_bytes(16), "Le mélangeur personnalisé n'a pas remplacé SHA3");
+    }
+
+    #[test]
+    fn test_generate_with_metadata() {
+        let mut rng = Yarrow::new(12345);
+        #[cfg(any(not(feature = "std"), feature = "deterministic-only"))]
+        rng.set_time_counter(3600);
+        let (small, small_meta) = rng.generate_with_metadata(8);
+        let (large, large_meta) = rng.generate_with_metadata(256);
+
+        assert_eq!(small.len(), 8);
+        assert_eq!(large.len(), 256);
+        assert!(large_meta.hash_ops > small_meta.hash_ops, "hash_ops n'augmente pas avec count");
+        assert!(small_meta.reseeded, "Le premier appel doit mettre à jour la graine");
+        assert!(!large_meta.reseeded, "La graine ne doit pas changer dans la fenêtre de 60 secondes");
+        assert_eq!(large_meta.entropy_bits_after, 0);
+
+        rng.reseed_bytes(&67890u64.to_be_bytes());
+        let (_, meta) = rng.generate_with_metadata(1);
+        assert_eq!(meta.entropy_bits_after, 64);
+    }
+
+    #[test]
+    fn test_fill_image() {
+        let mut rng = Yarrow::new_deterministic(12345);
+        let mut reference = Yarrow::new_deterministic(12345);
+        let mut image = vec![0u8; 5 * 3 * 3];
+        rng.fill_image(&mut image, 5, 3, 3);
+        assert_eq!(image, reference.generate_random_bytes(5 * 3 * 3), "Tous les octets de l'image n'ont pas été écrits");
+
+        let rgba = rng.generate_rgba_image(4, 2);
+        assert_eq!(rgba.len(), 2);
+        assert!(rgba.iter().all(|row| row.len() == 4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Image buffer length")]
+    fn test_fill_image_invalid_dimensions() {
+        let mut rng = Yarrow::new(12345);
+        let mut image = vec![0u8; 10];
+        rng.fill_image(&mut image, 2, 2, 3);
+    }
+
+    fn roll_dice<R: RandomSource>(rng: &mut R) -> Vec<u8> {
+        let mut bytes = [0u8; 16];
+        rng.fill_bytes(&mut bytes);
+        bytes.iter().map(|byte| byte % 6 + 1).collect()
+    }
+
+    #[test]
+    fn test_record_replay() {
+        let mut original = Yarrow::new_deterministic(12345);
+        let mut recorder = Yarrow::new_deterministic(12345);
+        let mut replay = recorder.record(16);
+
+        assert_eq!(roll_dice(&mut original), roll_dice(&mut replay), "Le rejeu ne reproduit pas le flux d'origine");
+        assert_eq!(replay.try_fill_bytes(&mut [0u8; 1]), Err(PrngError::Exhausted));
+    }
+
+    #[test]
+    fn test_replay_from_u64s() {
+        fn draw_three<R: RandomSource>(rng: &mut R) -> [u64; 3] {
+            [rng.next_u64(), rng.next_u64(), rng.next_u64()]
+        }
+
+        let mut scripted = ReplayRng::from_u64s(&[7, u64::MAX], ExhaustedPolicy::Wrap);
+        assert_eq!(draw_three(&mut scripted), [7, u64::MAX, 7], "Les valeurs scriptées doivent être rejouées dans l'ordre");
+
+        let mut strict = ReplayRng::from_u64s(&[1, 2], ExhaustedPolicy::Error);
+        assert_eq!([strict.next_u64(), strict.next_u64()], [1, 2]);
+        assert_eq!(strict.try_fill_bytes(&mut [0u8; 8]), Err(PrngError::Exhausted));
+
+        let mut rng = Yarrow::new_deterministic(12345);
+        let mut reference = Yarrow::new_deterministic(12345);
+        let draws = draw_three(&mut rng);
+        assert_eq!(draws.to_vec(), (0..3).map(|_| reference.generate_random_number()).collect::<Vec<u64>>(), "Yarrow doit implémenter RandomSource");
+    }
+
+    #[test]
+    fn test_replay_wrap() {
+        let mut replay = ReplayRng::new(vec![1, 2, 3], ExhaustedPolicy::Wrap);
+        let mut buf = [0u8; 7];
+        replay.fill_bytes(&mut buf);
+        assert_eq!(buf, [1, 2, 3, 1, 2, 3, 1]);
+
+        let mut empty = ReplayRng::new(Vec::new(), ExhaustedPolicy::Wrap);
+        assert_eq!(empty.try_fill_bytes(&mut buf), Err(PrngError::Exhausted));
+    }
+
+    #[test]
+    fn test_choose_weighted_index_prefix() {
+        let mut rng = Yarrow::new(12345);
+        let mut counts = [0usize; 3];
+        for _ in 0..2000 {
+            counts[rng.choose_weighted_index_prefix(&[1, 1, 4]).unwrap()] += 1;
+        }
+        assert_eq!(counts[1], 0, "Un poids nul ne doit jamais être choisi");
+        assert!((300..700).contains(&counts[0]), "Répartition inattendue : {:?}", counts);
+        assert!((1300..1700).contains(&counts[2]), "Répartition inattendue : {:?}", counts);
+
+        assert_eq!(rng.choose_weighted_index_prefix(&[]), None);
+        assert_eq!(rng.choose_weighted_index_prefix(&[0, 0]), None);
+        assert_eq!(rng.choose_weighted_index_prefix(&[0, 5]), Some(1));
+    }
+
+    #[cfg(feature = "std")]
+    fn hue_of((r, g, b): (u8, u8, u8)) -> f64 {
+        let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+        let sector = if max == r {
+            ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / delta + 2.0
+        } else {
+            (r - g) / delta + 4.0
+        };
+        sector / 6.0
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_generate_distinct_palette() {
+        let mut rng = Yarrow::new(12345);
+        let palette = rng.generate_distinct_palette(6);
+        assert_eq!(palette.len(), 6);
+
+        let hues: Vec<f64> = palette.iter().map(|&color| hue_of(color)).collect();
+        for (i, a) in hues.iter().enumerate() {
+            for b in &hues[i + 1..] {
+                let distance = (a - b).abs().min(1.0 - (a - b).abs());
+                assert!(distance > 0.05, "Teintes trop proches : {:?}", hues);
+            }
+        }
+        assert_eq!(hsl_to_rgb(0.0, 1.0, 0.5), (255, 0, 0));
+    }
+
+    #[cfg(feature = "rand-compat")]
+    #[test]
+    fn test_crypto_rng_bound() {
+        use rand_core::{CryptoRng, RngCore};
+
+        fn generate_secret_key<R: RngCore + CryptoRng>(rng: &mut R) -> [u8; 32] {
+            let mut key = [0u8; 32];
+            rng.fill_bytes(&mut key);
+            key
+        }
+
+        let mut rng = Yarrow::new(12345);
+        assert_ne!(generate_secret_key(&mut rng), [0u8; 32]);
+    }
+
+    #[cfg(feature = "rand-compat")]
+    #[test]
+    fn test_seedable_rng() {
+        use rand_core::SeedableRng;
+
+        let mut a = Yarrow::from_seed([7; 32]);
+        let mut b = Yarrow::from_seed([7; 32]);
+        let mut other = Yarrow::from_seed([8; 32]);
+        let first = a.generate_random_number();
+        assert_eq!(first, b.generate_random_number(), "La même graine doit donner le même flux");
+        assert_ne!(first, other.generate_random_number(), "Des graines différentes doivent donner des flux différents");
+
+        let mut from_u64 = Yarrow::seed_from_u64(42);
+        assert_eq!(from_u64.generate_random_number(), Yarrow::seed_from_u64(42).generate_random_number());
+    }
+
+    #[test]
+    fn test_deterministic_from() {
+        let mut rng = Yarrow::deterministic_from(vec![0, 0, 0, 0, 0, 0, 0, 7, 9]);
+        assert_eq!(rng.generate_random_number(), 7);
+        assert_eq!(rng.generate_random_bytes(3), vec![9, 0, 0]);
+
+        let mut strict = Yarrow::deterministic_from_with_policy(vec![1, 2, 3], ExhaustedPolicy::Error);
+        assert_eq!(strict.generate_random_bytes(3), vec![1, 2, 3]);
+        assert_eq!(strict.try_fill_bytes(&mut [0u8; 1]), Err(PrngError::Exhausted));
+    }
+
+    #[test]
+    fn test_instance_separation() {
+        let mut first = Yarrow::new(42);
+        let mut second = Yarrow::new(42);
+        assert_ne!(first.generate_random_bytes(16), second.generate_random_bytes(16), "Deux instances de même graine produisent le même flux");
+
+        let mut first = Yarrow::new_deterministic(42);
+        let mut second = Yarrow::new_deterministic(42);
+        assert_eq!(first.generate_random_bytes(16), second.generate_random_bytes(16), "new_deterministic n'est pas reproductible");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_shannon_entropy_estimate() {
+        let mut rng = Yarrow::new(12345);
+        let entropy = rng.shannon_entropy_estimate(16384);
+        assert!(entropy > 7.9 && entropy <= 8.0, "Entropie trop faible : {}", entropy);
+        assert_eq!(rng.shannon_entropy_estimate(0), 0.0);
+
+        let mut stuck = Yarrow::deterministic_from(vec![7]);
+        assert_eq!(stuck.shannon_entropy_estimate(1024), 0.0);
+    }
+
+    #[test]
+    fn test_iter_bytes_keeps_buffer() {
+        let mut rng = Yarrow::new_deterministic(12345);
+        let mut reference = Yarrow::new_deterministic(12345);
+        let block = reference.generate_random_bytes(BLOCK_BYTES);
+
+        let taken: Vec<u8> = rng.iter_bytes().take(3).collect();
+        assert_eq!(taken, block[..3]);
+        assert_eq!(rng.next_byte(), block[3], "Les octets restants du bloc ont été perdus");
+    }
+
+    #[test]
+    fn test_markov_walk() {
+        let mut rng = Yarrow::new(12345);
+        let transition = vec![vec![0.9, 0.1], vec![0.5, 0.5]];
+        let states = rng.markov_walk(&transition, 0, 5000);
+        assert_eq!(states.len(), 5000);
+
+        let in_first = states.iter().filter(|&&state| state == 0).count() as f64 / states.len() as f64;
+        assert!((in_first - 5.0 / 6.0).abs() < 0.05, "Distribution stationnaire non atteinte : {}", in_first);
+    }
+
+    #[test]
+    #[should_panic(expected = "square")]
+    fn test_markov_walk_invalid_matrix() {
+        let mut rng = Yarrow::new(12345);
+        rng.markov_walk(&[vec![0.5, 0.5], vec![1.0]], 0, 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_sharded_yarrow() {
+        let sharded = ShardedYarrow::with_shards(12345, 4);
+        assert_eq!(sharded.shard_count(), 4);
+
+        let outputs: Vec<Vec<u8>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| (0..40).flat_map(|_| sharded.generate_random_bytes(4)).collect::<Vec<u8>>()))
+                .collect();
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+        });
+        assert!(outputs.iter().all(|output| output.len() == 160));
+        for (i, a) in outputs.iter().enumerate() {
+            for b in &outputs[i + 1..] {
+                assert_ne!(a, b, "Deux threads ont reçu le même flux");
+            }
+        }
+
+        let shard_outputs: Vec<Vec<u8>> = sharded.shards.iter()
+            .map(|shard| shard.lock().unwrap().generate_random_bytes(16))
+            .collect();
+        for (i, a) in shard_outputs.iter().enumerate() {
+            for b in &shard_outputs[i + 1..] {
+                assert_ne!(a, b, "Deux shards produisent le même flux");
+            }
+        }
+    }
+
+    #[cfg(target_has_atomic = "64")]
+    #[test]
+    fn test_sequenced_yarrow() {
+        let sequenced = SequencedYarrow::new(12345);
+        let mut outputs: Vec<(u64, Vec<u8>)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| (0..10).map(|_| sequenced.generate_random_bytes(8)).collect::<Vec<_>>()))
+                .collect();
+            handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+        });
+        outputs.sort_by_key(|&(sequence, _)| sequence);
+
+        let single_threaded = SequencedYarrow::new(12345);
+        let expected: Vec<(u64, Vec<u8>)> = (0..40).map(|_| single_threaded.generate_random_bytes(8)).collect();
+        assert_eq!(outputs, expected, "Le flux trié par séquence doit être reproductible");
+        assert_ne!(expected[0].1, expected[1].1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_generate_log_uniform_duration() {
+        let mut rng = Yarrow::new(12345);
+        let (min, max) = (Duration::from_millis(1), Duration::from_secs(10));
+        let mut decades = [0usize; 4];
+        for _ in 0..2000 {
+            let duration = rng.generate_log_uniform_duration(min, max);
+            assert!(duration >= min && duration <= max, "Durée hors limites : {:?}", duration);
+            let decade = (duration.as_secs_f64() * 1000.0).log10().floor() as usize;
+            decades[decade.min(3)] += 1;
+        }
+        assert!(decades.iter().all(|&count| (350..650).contains(&count)), "Décennies déséquilibrées : {:?}", decades);
+
+        let fixed = Duration::from_millis(5);
+        assert_eq!(rng.generate_log_uniform_duration(fixed, fixed), fixed);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic(expected = "greater than zero")]
+    fn test_generate_log_uniform_duration_zero_min() {
+        let mut rng = Yarrow::new(12345);
+        rng.generate_log_uniform_duration(Duration::ZERO, Duration::from_secs(1));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_quota_yarrow() {
+        let mut quota = QuotaYarrow::new(Yarrow::new(12345), 32, Duration::from_secs(3600));
+        assert_eq!(quota.generate_random_bytes(&"a", 20).unwrap().len(), 20);
+        assert_eq!(quota.generate_random_bytes(&"a", 20), Err(PrngError::QuotaExceeded));
+        assert_eq!(quota.remaining(&"a"), 12);
+        assert_eq!(quota.generate_random_bytes(&"b", 32).unwrap().len(), 32, "Le quota d'un autre locataire a été affecté");
+
+        quota.reset_quota(&"a");
+        assert_eq!(quota.generate_random_bytes(&"a", 32).unwrap().len(), 32);
+        quota.reset_all_quotas();
+        assert_eq!(quota.remaining(&"b"), 32);
+
+        let mut short = QuotaYarrow::new(Yarrow::new(12345), 8, Duration::ZERO);
+        assert!(short.generate_random_bytes(&"a", 8).is_ok());
+        assert!(short.generate_random_bytes(&"a", 8).is_ok(), "La fenêtre expirée n'a pas été renouvelée");
+    }
+
+    #[test]
+    fn test_generate_latin_square() {
+        let mut rng = Yarrow::new(12345);
+        for n in 0..7 {
+            let square = rng.generate_latin_square(n);
+            assert_eq!(square.len(), n);
+            let expected: Vec<usize> = (0..n).collect();
+            for i in 0..n {
+                let mut row = square[i].clone();
+                let mut column: Vec<usize> = square.iter().map(|row| row[i]).collect();
+                row.sort();
+                column.sort();
+                assert_eq!(row, expected, "Ligne {} invalide : {:?}", i, square);
+                assert_eq!(column, expected, "Colonne {} invalide : {:?}", i, square);
+            }
+        }
+    }
+
+    #[test]
+    fn test_choose_stable() {
+        let set: BTreeSet<u32> = (0..100).collect();
+        let mut first = Yarrow::new_deterministic(12345);
+        let mut second = Yarrow::new_deterministic(12345);
+        assert_eq!(first.choose_stable(&set), second.choose_stable(&set), "La sélection n'est pas reproductible");
+        assert!(set.contains(first.choose_stable(&set).unwrap()));
+
+        let empty: BTreeSet<u32> = BTreeSet::new();
+        assert_eq!(first.choose_stable(&empty), None);
+    }
+
+    #[test]
+    fn test_deterministic_only_streams() {
+        let mut first = Yarrow::new_deterministic(12345);
+        let mut second = Yarrow::new_deterministic(12345);
+        for step in 0..20 {
+            first.set_time_counter(step * 45);
+            second.set_time_counter(step * 45);
+            assert_eq!(first.generate_random_bytes(32), second.generate_random_bytes(32), "Les flux divergent à l'étape {}", step);
+            assert_eq!(first.generate_random_number(), second.generate_random_number());
+        }
+    }
+
+    #[test]
+    fn test_split_streams() {
+        let mut parent = Yarrow::new_deterministic(12345);
+        let outputs: Vec<Vec<u8>> = parent.split_streams(4).iter_mut().map(|child| child.generate_random_bytes(16)).collect();
+        assert_eq!(outputs.len(), 4);
+        for (i, a) in outputs.iter().enumerate() {
+            for b in &outputs[i + 1..] {
+                assert_ne!(a, b, "Deux sous-générateurs produisent le même flux");
+            }
+        }
+
+        let mut same_parent = Yarrow::new_deterministic(12345);
+        let replayed: Vec<Vec<u8>> = same_parent.split_streams(4).iter_mut().map(|child| child.generate_random_bytes(16)).collect();
+        assert_eq!(outputs, replayed, "La division n'est pas reproductible");
+        assert_eq!(parent.pool, same_parent.pool, "Le parent doit avancer de façon déterministe");
+        assert!(parent.split_streams(0).is_empty());
+    }
+
+    #[test]
+    fn test_yarrow_shuffle_reproducible() {
+        let mut first = Yarrow::new_deterministic(12345);
+        let mut second = Yarrow::new_deterministic(12345);
+        let mut a: Vec<u32> = (0..10).collect();
+        let mut b = a.clone();
+        first.shuffle(&mut a);
+        second.shuffle(&mut b);
+        assert_eq!(a, b, "Le mélange n'est pas reproductible");
+
+        a.sort();
+        assert_eq!(a, (0..10).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn test_yarrow_shuffle_seed_dependent() {
+        let mut a: Vec<u32> = (0..10).collect();
+        let mut b = a.clone();
+        Yarrow::new_deterministic(1).shuffle(&mut a);
+        Yarrow::new_deterministic(2).shuffle(&mut b);
+        assert_ne!(a, b, "Deux graines différentes donnent le même mélange");
+    }
+
+    #[test]
+    fn test_yarrow_shuffle_pinned() {
+        let mut rng = Yarrow::new_deterministic(42);
+        let mut items: Vec<u32> = (0..10).collect();
+        rng.shuffle(&mut items);
+        assert_eq!(items, PINNED_SHUFFLE, "L'ordre du mélange de Fisher-Yates a changé");
+    }
+
+    #[test]
+    fn test_frame_iter() {
+        let mut rng = Yarrow::new(12345);
+        #[cfg(any(not(feature = "std"), feature = "deterministic-only"))]
+        rng.set_time_counter(3600);
+
+        let frames: Vec<(Vec<u8>, bool)> = rng.frame_iter(16).take(4).collect();
+        assert!(frames.iter().all(|(frame, _)| frame.len() == 16));
+        let flags: Vec<bool> = frames.iter().map(|&(_, reseeded)| reseeded).collect();
+        assert_eq!(flags, vec![true, false, false, false], "Seule la première trame doit déclencher la mise à jour de la graine");
+    }
+
+    #[test]
+    fn test_frame_iter_reports_interval_reseeds() {
+        let mut rng = Yarrow::new_deterministic(12345);
+        rng.set_reseed_interval(64);
+
+        let flags: Vec<bool> = rng.frame_iter(32).take(4).map(|(_, reseeded)| reseeded).collect();
+        assert_eq!(flags, vec![false, true, false, true], "Chaque réensemencement par intervalle doit être signalé");
+        assert_eq!(rng.stats().reseed_count, 2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_benford() {
+        let mut rng = Yarrow::new(12345);
+        let mut counts = [0usize; 10];
+        for _ in 0..5000 {
+            counts[rng.generate_benford_leading_digit() as usize] += 1;
+        }
+        assert_eq!(counts[0], 0);
+        for (digit, &count) in counts.iter().enumerate().skip(1) {
+            let expected = (1.0 + 1.0 / digit as f64).log10();
+            let observed = count as f64 / 5000.0;
+            assert!((observed - expected).abs() < 0.025, "Chiffre {} : {} au lieu de {}", digit, observed, expected);
+        }
+
+        for _ in 0..100 {
+            let number = rng.generate_benford_number(4);
+            assert!((10_000..100_000).contains(&number), "Nombre hors magnitude : {}", number);
+        }
+        assert!(rng.generate_benford_number(18) >= 10u64.pow(18));
+    }
+
+    #[test]
+    fn test_generate_chunked() {
+        for (total, chunk) in [(1000, 64), (1000, 1000), (1000, 3000), (0, 16)] {
+            let mut chunked = Yarrow::new_deterministic(12345);
+            let mut single = Yarrow::new_deterministic(12345);
+
+            let pieces: Vec<Vec<u8>> = chunked.generate_chunked(total, chunk).collect();
+            assert!(pieces.iter().all(|piece| piece.len() <= chunk), "Les morceaux ne doivent pas dépasser la taille demandée");
+            assert_eq!(pieces.concat(), single.generate_random_bytes(total), "La concaténation doit être identique à une génération unique");
+            assert_eq!((chunked.seed, &chunked.pool), (single.seed, &single.pool), "Les générateurs doivent rester synchronisés");
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_estimate_period_lower_bound() {
+        for seed in [0, 1, 42, 12345] {
+            let mut rng = Yarrow::new_deterministic(seed);
+            assert_eq!(rng.estimate_period_lower_bound(5000), PeriodEstimate::LowerBound(5000), "Aucun cycle ne doit être détecté");
+        }
+    }
+
+    #[test]
+    fn test_bounded() {
+        let mut rng = Yarrow::new(12345);
+        let mut bounded = rng.bounded(10);
+        let mut input = [0u8; 4];
+
+        assert!(bounded.try_fill_bytes(&mut input).is_ok());
+        assert!(bounded.try_fill_bytes(&mut input).is_ok());
+        assert_eq!(bounded.try_fill_bytes(&mut input), Err(PrngError::Exhausted), "Le budget doit être respecté");
+        assert_eq!(bounded.remaining(), 2, "Une requête refusée ne doit pas consommer le budget");
+        assert!(bounded.try_fill_bytes(&mut input[..2]).is_ok());
+        assert_eq!(bounded.try_fill_bytes(&mut input[..1]), Err(PrngError::Exhausted));
+        assert!(bounded.try_fill_bytes(&mut []).is_ok());
+    }
+
+    #[test]
+    fn test_gen_bounded_constant_time() {
+        let mut rng = Yarrow::new(12345);
+        let mut counts = [0usize; 10];
+        for _ in 0..5000 {
+            let number = rng.gen_bounded_constant_time(10, 19);
+            assert!((10..=19).contains(&number), "Le nombre généré est hors de la plage spécifiée");
+            counts[(number - 10) as usize] += 1;
+        }
+        for count in counts {
+            assert!((350..=650).contains(&count), "Distribution trop déséquilibrée : {:?}", counts);
+        }
+
+        assert_eq!(rng.gen_bounded_constant_time(7, 7), 7);
+        rng.gen_bounded_constant_time(0, u64::MAX);
+    }
+
+    #[test]
+    fn test_from_sources() {
+        let sources: [&[u8]; 4] = [b"os randomness", b"1700000000", b"user key", b"00:11:22:33:44:55"];
+        let reference = Yarrow::from_sources(&sources).generate_random_bytes(32);
+        assert_eq!(reference, Yarrow::from_sources(&sources).generate_random_bytes(32));
+
+        for i in 0..sources.len() {
+            let mut altered = sources;
+            altered[i] = b"altered";
+            assert_ne!(Yarrow::from_sources(&altered).generate_random_bytes(32), reference, "Chaque source doit influencer la sortie");
+        }
+
+        #[cfg(feature = "std")]
+        {
+            let zeros = [0u8; 64];
+            let mut rng = Yarrow::from_sources(&[b"os randomness", &zeros, b"user key"]);
+            let entropy = rng.shannon_entropy_estimate(4096);
+            assert!(entropy > 7.9, "Entropie trop faible avec une source nulle : {}", entropy);
+        }
+    }
+
+    #[cfg(feature = "trace-events")]
+    #[test]
+    fn test_event_sink() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink_events = Arc::clone(&events);
+        let mut rng = Yarrow::new(12345);
+        #[cfg(any(not(feature = "std"), feature = "deterministic-only"))]
+        rng.set_time_counter(3600);
+        rng.set_event_sink(Box::new(move |event| sink_events.lock().unwrap().push(*event)));
+
+        rng.reseed(67890);
+
+        let events = events.lock().unwrap();
+        assert_eq!(events[0], YarrowEvent::EntropyAdded { bits: 64, entropy_estimate: 64 });
+        assert_eq!(events[1], YarrowEvent::Reseeded { source: ReseedSource::External, entropy_estimate: 64 }, "Le réensemencement doit être signalé avec sa source");
+    }
+
+    #[test]
+    fn test_generate_fixed_q16_16() {
+        let mut rng = Yarrow::new(12345);
+        for _ in 0..1000 {
+            let value = rng.generate_fixed_q16_16();
+            assert!((0..0x10000).contains(&value), "La valeur doit être dans [0, 1)");
+
+            let bounded = rng.generate_fixed_q16_16_range(-0x28000, 0x28000);
+            assert!((-0x28000..0x28000).contains(&bounded), "La valeur doit être dans [-2.5, 2.5)");
+        }
+        assert_eq!(rng.generate_fixed_q16_16_range(i32::MIN, i32::MIN + 1), i32::MIN);
+        rng.generate_fixed_q16_16_range(i32::MIN, i32::MAX);
+    }
+
+    #[test]
+    fn test_generate_fixed_q16_16_pinned() {
+        let mut rng = Yarrow::new_deterministic(42);
+        let values: Vec<i32> = (0..4).map(|_| rng.generate_fixed_q16_16()).collect();
+        assert_eq!(values, PINNED_FIXED_Q16_16, "Le flux en virgule fixe a changé");
+    }
+
+    #[test]
+    fn test_merge_entropy_from() {
+        let mut first_sensor = Yarrow::new_deterministic(1);
+        first_sensor.add_entropy(111);
+        let mut second_sensor = Yarrow::new_deterministic(2);
+        second_sensor.add_entropy(222);
+
+        let mut merged = Yarrow::new_deterministic(42);
+        merged.merge_entropy_from(&first_sensor);
+        let mut other_merged = Yarrow::new_deterministic(42);
+        other_merged.merge_entropy_from(&second_sensor);
+        assert_ne!(merged.pool, other_merged.pool, "Des sources différentes doivent donner des états différents");
+        assert_eq!(merged.entropy_estimate, 64);
+
+        let mut unmerged = Yarrow::new_deterministic(42);
+        assert_ne!(merged.generate_random_bytes(32), unmerged.generate_random_bytes(32), "La fusion doit modifier la sortie");
+        assert_ne!(merged.generate_random_bytes(32), first_sensor.generate_random_bytes(32));
+    }
+
+    #[test]
+    fn test_keystream_xor() {
+        let plaintext = b"Le message secret a transmettre".to_vec();
+
+        let mut ciphertext = plaintext.clone();
+        Yarrow::new_deterministic(42).keystream_xor(&mut ciphertext);
+        assert_ne!(ciphertext, plaintext);
+
+        let mut other_ciphertext = plaintext.clone();
+        Yarrow::new_deterministic(43).keystream_xor(&mut other_ciphertext);
+        assert_ne!(ciphertext, other_ciphertext, "Des graines différentes doivent donner des chiffrés différents");
+
+        Yarrow::new_deterministic(42).keystream_xor(&mut ciphertext);
+        assert_eq!(ciphertext, plaintext, "Le déchiffrement doit restituer le message");
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut rng = Yarrow::new_deterministic(12345);
+        assert_eq!(rng.stats(), YarrowStats { bytes_generated: 0, reseed_count: 0, last_reseed_time: 0 });
+
+        rng.generate_random_bytes(100);
+        rng.fill(&mut [0u8; 28]);
+        assert_eq!(rng.stats().bytes_generated, 128, "Le nombre d'octets générés est incorrect");
+
+        rng.reseed(7);
+        assert_eq!(rng.stats().reseed_count, 0, "Aucun réensemencement avant la fin de la fenêtre");
+        rng.set_time_counter(61);
+        rng.reseed(7);
+        assert_eq!(rng.stats().reseed_count, 1, "reseed doit incrémenter reseed_count");
+        assert_eq!(rng.stats().last_reseed_time, 61);
+
+        rng.set_reseed_interval(64);
+        rng.generate_random_bytes(64);
+        assert_eq!(rng.stats().reseed_count, 2, "Le réensemencement par intervalle doit être compté");
+        assert_eq!(rng.stats().bytes_generated, 192);
+
+        let restored = Yarrow::from_snapshot(&rng.to_snapshot()).unwrap();
+        assert_eq!(restored.stats(), rng.stats(), "Les statistiques doivent survivre à l'instantané");
+    }
+
+    #[test]
+    fn test_rekey_threshold() {
+        let mut rng = Yarrow::builder(42).rekey_threshold(100).build();
+        rng.generate_random_bytes(60);
+        assert_eq!(rng.rekey_count(), 0);
+        rng.generate_random_bytes(60);
+        assert_eq!(rng.rekey_count(), 1, "Le seuil franchi doit déclencher un changement de clé");
+        rng.generate_random_bytes(60);
+        assert_eq!(rng.rekey_count(), 1);
+
+        let mut first = Yarrow::builder(42).rekey_threshold(100).build();
+        let mut second = Yarrow::builder(42).rekey_threshold(100).build();
+        let mut unkeyed = Yarrow::builder(42).build();
+        assert_eq!(first.generate_random_bytes(150), second.generate_random_bytes(150));
+        unkeyed.generate_random_bytes(150);
+        assert_eq!((first.seed, &first.pool), (second.seed, &second.pool), "La sortie doit rester déterministe");
+        assert_ne!(first.pool, unkeyed.pool);
+        assert_eq!(unkeyed.rekey_count(), 0);
+    }
+
+    #[test]
+    fn test_generate_bounded_batch() {
+        let mut rng = Yarrow::new(12345);
+        for n in [0, 1, 7, 100] {
+            assert_eq!(rng.generate_bounded_batch(10, 20, n).len(), n, "Le lot doit contenir n valeurs");
+        }
+
+        let samples = 6000;
+        let batch = rng.generate_bounded_batch(1, 6, samples);
+        assert!(batch.iter().all(|value| (1..=6).contains(value)), "Valeur hors de l'intervalle");
+        let mut counts = [0usize; 6];
+        for value in batch {
+            counts[(value - 1) as usize] += 1;
+        }
+        assert!(counts.iter().all(|&count| (850..1150).contains(&count)), "Répartition non uniforme : {:?}", counts);
+
+        assert!(rng.generate_bounded_batch(5, 5, 10).iter().all(|&value| value == 5));
+        assert_eq!(rng.generate_bounded_batch(0, u64::MAX, 3).len(), 3);
+        // A zero draw is rejected for a span of 2^63 + 1 and replaced by the next word of the stream.
+        let span = (1u64 << 63) + 1;
+        let words = [u64::MAX, 0, u64::MAX - 5, 1 << 63];
+        let script: Vec<u8> = words.iter().flat_map(|word| word.to_be_bytes()).collect();
+        let mut scripted = Yarrow::deterministic_from_with_policy(script, ExhaustedPolicy::Error);
+        assert_eq!(scripted.generate_bounded_batch(0, span - 1, 3), vec![u64::MAX % span, (u64::MAX - 5) % span, (1 << 63) % span]);
+    }
+
+    #[test]
+    fn test_generate_many_u64_endianness() {
+        let big = Yarrow::new_deterministic(42).generate_many_u64(16);
+        let little = Yarrow::new_deterministic(42).generate_many_u64_with(16, Endianness::Little);
+
+        assert_eq!(big.len(), 16);
+        assert_eq!(big, Yarrow::new_deterministic(42).generate_many_u64_with(16, Endianness::Big));
+        for (big_word, little_word) in big.iter().zip(&little) {
+            assert_eq!(big_word.swap_bytes(), *little_word, "Les mots doivent être inversés octet par octet");
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_laplace_and_privacy_accountant() {
+        let mut rng = Yarrow::new(12345);
+        let mut accountant = PrivacyAccountant::new();
+        for _ in 0..3 {
+            assert!(accountant.laplace(&mut rng, 1.0, 0.5).is_finite());
+        }
+        assert_eq!(accountant.draws(), 3);
+        assert!((accountant.budget_spent() - 1.5).abs() < 1e-12, "Le budget consommé doit s'accumuler");
+
+        let scale = 2.0;
+        let samples: Vec<f64> = (0..10_000).map(|_| rng.generate_laplace(scale)).collect();
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+        assert!(mean.abs() < 0.15, "Moyenne inattendue : {}", mean);
+        assert!((variance / (2.0 * scale * scale) - 1.0).abs() < 0.15, "Variance inattendue : {}", variance);
+    }
+
+    #[test]
+    fn test_tee() {
+        let mut rng = Yarrow::new(12345);
+        rng.generate_random_bytes(10);
+        rng.next_byte();
+
+        let (mut left, mut right) = rng.tee();
+        let left_bytes: Vec<u8> = (0..100).map(|_| left.next_byte()).collect();
+        let right_bytes: Vec<u8> = (0..100).map(|_| right.next_byte()).collect();
+        assert_eq!(left_bytes, right_bytes, "Les deux copies doivent produire le même flux");
+
+        let (mut left, right) = rng.tee();
+        left.generate_random_bytes(64);
+        assert_eq!(right.buffer_pos, rng.buffer_pos, "Avancer une copie ne doit pas affecter l'autre");
+        assert_eq!((right.seed, &right.pool), (rng.seed, &rng.pool));
+    }
+
+    #[test]
+    fn test_gen_bounded_checked() {
+        let mut rng = Yarrow::new(12345);
+        assert_eq!(rng.gen_bounded_checked(20, 10), None, "Une plage inversée doit être refusée");
+        assert_eq!(rng.gen_bounded_checked(u64::MAX, 0), None);
+        assert_eq!(rng.gen_bounded_checked(7, 7), Some(7));
+        assert_eq!(rng.gen_bounded_checked(u64::MAX, u64::MAX), Some(u64::MAX));
+        assert!(rng.gen_bounded_checked(0, u64::MAX).is_some(), "La plage complète doit être acceptée");
+        assert!(rng.gen_bounded_checked(1, u64::MAX).is_some());
+        for _ in 0..100 {
+            let number = rng.gen_bounded_checked(10, 20).unwrap();
+            assert!((10..=20).contains(&number), "Le nombre généré est hors de la plage spécifiée");
+        }
+
+        let mut exhausted = Yarrow::deterministic_from_with_policy(vec![0; 4], ExhaustedPolicy::Error);
+        assert_eq!(exhausted.gen_bounded_checked(0, 9), None, "Une source épuisée ne doit pas provoquer de panique");
+    }
+
+    #[test]
+    fn test_shuffle_bias_report() {
+        let mut rng = Yarrow::new(12345);
+        let (n, trials) = (4, 2000);
+        let report = rng.shuffle_bias_report(n, trials);
+
+        assert!(report.iter().all(|row| row.iter().sum::<usize>() == trials));
+        let expected = trials as f64 / n as f64;
+        let chi_squared: f64 = report.iter()
+            .flatten()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+        // 9 degrees of freedom: 45.0 is the critical value at p ≈ 1e-6, so a
+        // sound shuffle fails this only by astronomically bad luck.
+        assert!(chi_squared < 45.0, "Mélange biaisé : chi² = {} pour {:?}", chi_squared, report);
+    }
+
+    #[test]
+    fn test_generate_small() {
+        for count in [0, 16, INLINE_BYTES, INLINE_BYTES + 1, 100] {
+            let small = Yarrow::new_deterministic(12345).generate_small(count);
+            let expected = Yarrow::new_deterministic(12345).generate_random_bytes(count);
+
+            assert_eq!(small.is_inline(), count <= INLINE_BYTES, "Seules les petites sorties doivent être stockées en ligne");
+            assert_eq!(&*small, &expected[..], "La sortie doit être identique à generate_random_bytes");
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_generate_f64_full() {
+        let mut rng = Yarrow::new(12345);
+        for _ in 0..1000 {
+            let value = rng.generate_f64_full();
+            assert!((0.0..1.0).contains(&value), "La valeur doit être dans [0, 1)");
+        }
+
+        let mut script = vec![0u8; 7];
+        script.push(1);
+        script.extend([0xFF; 8]);
+        let expected = ((1u64 << 53) - 1) as f64 * 2f64.powi(-53) * 2f64.powi(-63);
+        let value = Yarrow::deterministic_from(script.clone()).generate_f64_full();
+        assert_eq!(value, expected);
+        assert!(value > 0.0 && value < 2f64.powi(-53), "Les valeurs inférieures à 2^-53 doivent être atteignables");
+        assert_eq!(Yarrow::deterministic_from(script).generate_f64(), 0.0);
+
+        let tiny = Yarrow::deterministic_from(vec![0]).generate_f64_full();
+        assert!(tiny < 2f64.powi(-128), "Deux tirages nuls doivent donner une valeur inférieure à 2^-128");
+    }
+
+    #[test]
+    fn test_draw_count() {
+        fn draws_of(rng: &mut Yarrow, sampler: impl FnOnce(&mut Yarrow)) -> u64 {
+            let before = rng.draw_count();
+            sampler(rng);
+            rng.draw_count() - before
+        }
+
+        let mut rng = Yarrow::new(12345);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.generate_random_number(); }), 1);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.generate_bounded_number(10, 20); }), 1);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.gen_bounded_constant_time(10, 20); }), 1);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.generate_bounded_number_ct(10, 20); }), 4);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.gen_in(10..20); }), 1);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.generate_range(10u8, 25); }), 1);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.generate_range(-10i64, 20); }), 1);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.generate_range(10u128, 20); }), 2);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.generate_f64(); }), 1);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.generate_f32(); }), 1);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.gen_bool(0.25); }), 1);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.gen_ratio(1, 3); }), 1);
+        #[cfg(feature = "std")]
+        assert_eq!(draws_of(&mut rng, |rng| { rng.generate_laplace(1.0); }), 1);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.generate_fixed_q16_16(); }), 1);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.generate_fixed_q16_16_range(-10, 10); }), 1);
+        #[cfg(feature = "std")]
+        assert_eq!(draws_of(&mut rng, |rng| { rng.generate_benford_leading_digit(); }), 1);
+        #[cfg(feature = "std")]
+        assert_eq!(draws_of(&mut rng, |rng| { rng.generate_benford_number(3); }), 1);
+        #[cfg(feature = "std")]
+        assert_eq!(draws_of(&mut rng, |rng| { rng.generate_log_uniform_duration(Duration::from_millis(1), Duration::from_secs(1)); }), 1);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.choose_weighted_index_prefix(&[1, 4, 6]); }), 1);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.weighted_index(&[1, 3, 2]); }), 1);
+        #[cfg(feature = "std")]
+        assert_eq!(draws_of(&mut rng, |rng| { rng.generate_f64_full(); }), 2);
+        #[cfg(feature = "std")]
+        assert_eq!(draws_of(&mut rng, |rng| { rng.generate_normal(0.0, 1.0); }), 2);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.shuffle(&mut [0; 10]); }), 9);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.random_permutation(10); }), 9);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.iter_u64().take(3).for_each(drop); }), 3);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.partial_shuffle(&mut [0; 10], 3); }), 3);
+        assert_eq!(draws_of(&mut rng, |rng| { rng.partial_shuffle(&mut [0; 10], 10); }), 9);
+
+        // Bounds rejecting almost half of the draws still cost one draw per call.
+        assert_eq!(draws_of(&mut rng, |rng| { (0..100).for_each(|_| { rng.gen_bounded_checked(0, 1 << 63); }); }), 100);
+        assert_eq!(draws_of(&mut rng, |rng| { (0..100).for_each(|_| { rng.gen_index((1usize << 63) + 1); }); }), 100);
+        assert_eq!(draws_of(&mut rng, |rng| { (0..100).for_each(|_| { rng.generate_bounded_number(0, 1 << 63); }); }), 100);
+        assert_eq!(draws_of(&mut rng, |rng| { (0..100).for_each(|_| { rng.generate_range(0u8, 128); }); }), 100);
+
+        // Two draws, then one per further 64 zero bits until the subnormal range is exhausted.
+        #[cfg(feature = "std")]
+        {
+            let mut zeros = Yarrow::deterministic_from(vec![0]);
+            assert_eq!(draws_of(&mut zeros, |rng| { rng.generate_f64_full(); }), 18);
+        }
+    }
+
+    #[test]
+    fn test_generate_bounded_number_uniform() {
+        let mut rng = Yarrow::new(12345);
+        let samples = 12_000;
+        let mut counts = [0usize; 3];
+        for _ in 0..samples {
+            let value = rng.generate_bounded_number(0, 2);
+            assert!(value <= 2, "Valeur hors de l'intervalle : {}", value);
+            counts[value as usize] += 1;
+        }
+
+        let expected = samples as f64 / 3.0;
+        let chi_squared: f64 = counts.iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+        // 2 degrees of freedom: 27.6 is the critical value at p = 1e-6.
+        assert!(chi_squared < 27.6, "Distribution biaisée : chi² = {} pour {:?}", chi_squared, counts);
+    }
+
+    #[test]
+    fn test_generate_bounded_number_rejects_biased_tail() {
+        let span = (1u64 << 63) + 1;
+        let threshold = span.wrapping_neg() % span;
+        let next = u64::MAX - 5;
+        let mut script = (threshold - 1).to_be_bytes().to_vec();
+        script.extend(next.to_be_bytes());
+        let mut rng = Yarrow::deterministic_from_with_policy(script, ExhaustedPolicy::Error);
+
+        let value = rng.generate_bounded_number(0, span - 1);
+        assert!(value < span, "Valeur hors de l'intervalle : {}", value);
+        assert_eq!(rng.draw_count(), 1, "Un rejet ne doit pas consommer de tirage supplémentaire");
+        assert_eq!(rng.generate_random_number(), next, "Le tirage suivant du flux doit rester aligné");
+    }
+
+    #[test]
+    fn test_generate_bounded_number_edge_ranges() {
+        let draw = Yarrow::new_deterministic(12345).generate_random_number();
+        let mut rng = Yarrow::new_deterministic(12345);
+        assert_eq!(rng.generate_bounded_number(0, u64::MAX), draw, "L'intervalle complet doit renvoyer le tirage tel quel");
+
+        let mut rng = Yarrow::new(12345);
+        assert_eq!(rng.generate_bounded_number(5, 5), 5, "Un intervalle d'une valeur doit renvoyer min");
+        assert_eq!(rng.try_generate_bounded_number(5, 5), Ok(5));
+
+        let draws = rng.draw_count();
+        assert_eq!(rng.try_generate_bounded_number(10, 5), Err(PrngError::InvalidRange));
+        assert_eq!(rng.draw_count(), draws, "Un intervalle rejeté ne doit pas consommer de tirage");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid range")]
+    fn test_generate_bounded_number_inverted_bounds() {
+        Yarrow::new(12345).generate_bounded_number(10, 5);
+    }
+
+    #[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+    #[test]
+    fn test_from_os_entropy() {
+        let mut first = Yarrow::from_os_entropy().expect("Entropie du système indisponible");
+        let mut second = Yarrow::from_os_entropy().expect("Entropie du système indisponible");
+        assert_eq!(first.entropy_estimate, 512);
+        assert_ne!(first.generate_random_bytes(32), second.generate_random_bytes(32), "Deux instances produisent la même sortie");
+    }
+
+    #[test]
+    fn test_generate_random_bytes_length() {
+        let mut rng = Yarrow::new(12345);
+        for count in [0, 1, 63, 64, 65, 128, 1000] {
+            assert_eq!(rng.generate_random_bytes(count).len(), count, "La sortie doit contenir exactement count octets");
+        }
+    }
+
+    #[test]
+    fn test_generate_random_bytes_chi_square() {
+        let mut rng = Yarrow::new_deterministic(12345);
+        let samples = 256 * 1000;
+        let mut counts = [0u64; 256];
+        for byte in rng.generate_random_bytes(samples) {
+            counts[byte as usize] += 1;
+        }
+
+        let expected = (samples / 256) as f64;
+        let chi_square: f64 = counts.iter().map(|&count| (count as f64 - expected).powi(2) / expected).sum();
+        // 330.5 is the 0.999 quantile of the chi-square distribution with 255 degrees of freedom.
+        assert!(chi_square < 330.5, "Distribution des octets non uniforme : chi² = {}", chi_square);
+    }
+
+    #[test]
+    fn test_generate_random_bytes_block_cost() {
+        let mut rng = Yarrow::new(12345);
+        let count = 64 * 1024;
+        let hash_ops_before = rng.hash_ops;
+        rng.generate_random_bytes(count);
+
+        // One mixer call per 64-byte block, plus a constant number for keying and feedback.
+        let hash_ops = rng.hash_ops - hash_ops_before;
+        assert!(hash_ops <= (count / 64) as u64 + 4, "Trop d'appels au mélangeur : {} pour {} octets", hash_ops, count);
+    }
+
+    /// Measures the throughput of `generate_random_bytes`; run with
+    /// `cargo test --release -- --ignored --nocapture test_generate_random_bytes_throughput`.
+    #[cfg(feature = "std")]
+    #[test]
+    #[ignore]
+    fn test_generate_random_bytes_throughput() {
+        let mut rng = Yarrow::new(12345);
+        let count = 16 * 1024 * 1024;
+        let start = Instant::now();
+        let bytes = rng.generate_random_bytes(count);
+        let elapsed = start.elapsed();
+
+        assert_eq!(bytes.len(), count);
+        let throughput = count as f64 / elapsed.as_secs_f64().max(f64::EPSILON) / 1_000_000.0;
+        println!("{} octets en {:?} ({:.1} Mo/s)", count, elapsed, throughput);
+    }
+
+    #[test]
+    fn test_fill_bytes_inherent() {
+        let (mut left, mut right) = Yarrow::new(12345).tee();
+        let mut nonces = [0u8; 36];
+        for slot in nonces.chunks_mut(12) {
+            left.fill_bytes(slot);
+        }
+        let expected: Vec<u8> = (0..3).flat_map(|_| right.generate_random_bytes(12)).collect();
+        assert_eq!(nonces.to_vec(), expected, "fill_bytes doit produire les mêmes octets que generate_random_bytes");
+    }
+
+    #[test]
+    fn test_gen_array() {
+        let mut rng = Yarrow::new(12345);
+        let small = rng.gen_array::<16>();
+        let block = rng.gen_array::<64>();
+        let large = rng.gen_array::<100>();
+        assert_eq!((small.len(), block.len(), large.len()), (16, 64, 100));
+        assert!(small.iter().any(|&byte| byte != 0), "Le tableau ne doit pas être nul");
+        assert!(block.iter().any(|&byte| byte != 0), "Le tableau ne doit pas être nul");
+        assert!(large[64..].iter().any(|&byte| byte != 0), "Le second bloc ne doit pas être nul");
+        assert_eq!(rng.gen_array::<0>(), [0u8; 0]);
+
+        let (mut left, mut right) = rng.tee();
+        assert_eq!(left.gen_array::<100>().to_vec(), right.generate_random_bytes(100), "gen_array doit produire les mêmes octets que generate_random_bytes");
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut rng = Yarrow::new(12345);
+        let mut large = [0u8; 1000];
+        rng.fill(&mut large);
+        assert!(large.iter().any(|&byte| byte != 0), "Le tampon est resté à zéro");
+
+        let mut small = [0u8; 3];
+        rng.fill(&mut small);
+        rng.fill(&mut []);
+
+        let mut buf = [0u8; 130];
+        Yarrow::new_deterministic(12345).fill(&mut buf);
+        assert_eq!(buf.to_vec(), Yarrow::new_deterministic(12345).generate_random_bytes(130), "fill doit suivre le même flux que generate_random_bytes");
+    }
+
+    #[test]
+    fn test_reseed_clock_backwards() {
+        let mut rng = Yarrow::new(12345);
+        rng.last_reseed_time = u64::MAX;
+        let seed = rng.seed;
+        let pool = rng.pool.clone();
+
+        rng.reseed(67890);
+        assert_ne!(rng.pool, pool, "L'entropie doit être mélangée même si l'horloge recule");
+        assert_eq!(rng.seed, seed, "La graine ne doit pas changer quand l'horloge recule");
+        assert_eq!(rng.last_reseed_time, u64::MAX);
+        rng.generate_random_bytes(16);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_roundtrip() {
+        let mut rng = Yarrow::new_deterministic(12345);
+        rng.generate_random_bytes(100);
+        let serialized = serde_json::to_string(&rng).unwrap();
+        assert_eq!(serialized, format!("\"{}\"", hex::encode(rng.to_snapshot())), "L'état doit être encodé en hexadécimal");
+        let expected = rng.generate_random_bytes(200);
+
+        let mut restored: Yarrow = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(restored.generate_random_bytes(200), expected, "La désérialisation doit reprendre le même flux");
+        assert!(serde_json::from_str::<Yarrow>("\"00\"").is_err());
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let mut rng = Yarrow::new(12345);
+        rng.generate_random_bytes(100);
+        rng.next_byte();
+        rng.add_entropy(67890);
+        let snapshot = rng.to_snapshot();
+        let expected = rng.generate_random_bytes(200);
+        let expected_byte = rng.next_byte();
+
+        let mut restored = Yarrow::from_snapshot(&snapshot).expect("Instantané invalide");
+        assert_eq!(restored.generate_random_bytes(200), expected, "L'instantané ne reproduit pas le flux");
+        assert_eq!(restored.next_byte(), expected_byte);
+        assert_eq!(restored.draw_count(), rng.draw_count());
+        assert_eq!(restored.fortuna_pools, rng.fortuna_pools, "Les pools de Fortuna n'ont pas été restaurés");
+
+        // 1 version byte, 15 words, the clock and mixer flags, the 64-byte pool with its length, the buffer and its
+        // position, the accumulators with their lengths and pending bits, only the first one holding a
+        // digest, then the empty fast and slow pools and source table.
+        assert_eq!(snapshot.len(), 1 + 15 * 8 + 2 + 4 + 64 + 4 + BLOCK_BYTES + 4 + FORTUNA_POOLS * 8 + 64 + 3 * 4, "L'instantané n'est pas compact");
+        assert_eq!(Yarrow::from_snapshot(&snapshot[..snapshot.len() - 1]).err(), Some(PrngError::InvalidSnapshot));
+        let mut trailing = snapshot.clone();
+        trailing.push(0);
+        assert_eq!(Yarrow::from_snapshot(&trailing).err(), Some(PrngError::InvalidSnapshot));
+        let mut versioned = snapshot;
+        versioned[0] = 6;
+        assert_eq!(Yarrow::from_snapshot(&versioned).err(), Some(PrngError::InvalidSnapshot));
+    }
+
+    #[test]
+    fn test_generate_f64() {
+        let mut rng = Yarrow::new(12345);
+        let samples = 10_000;
+        let mut sum = 0.0;
+        for _ in 0..samples {
+            let value = rng.generate_f64();
+            assert!((0.0..1.0).contains(&value), "La valeur doit être dans [0, 1)");
+            sum += value;
+        }
+        // The standard deviation of the mean is about 0.003.
+        let mean = sum / samples as f64;
+        assert!((mean - 0.5).abs() < 0.02, "Moyenne trop éloignée de 0.5 : {}", mean);
+        assert!(Yarrow::deterministic_from(vec![0xFF; 8]).generate_f64() < 1.0);
+    }
+
+    #[test]
+    fn test_gen_bool() {
+        let mut rng = Yarrow::new(12345);
+        let samples = 10_000;
+        let hits = (0..samples).filter(|_| rng.gen_bool(0.25)).count();
+        // The standard deviation of the count is about 43.
+        assert!((2300..2700).contains(&hits), "Proportion trop éloignée de 1/4 : {}", hits);
+
+        let ratio_hits = (0..samples).filter(|_| rng.gen_ratio(1, 4)).count();
+        assert!((2300..2700).contains(&ratio_hits), "Proportion trop éloignée de 1/4 : {}", ratio_hits);
+
+        for _ in 0..1000 {
+            assert!(!rng.gen_bool(0.0), "gen_bool(0.0) ne doit jamais renvoyer true");
+            assert!(rng.gen_bool(1.0), "gen_bool(1.0) doit toujours renvoyer true");
+            assert!(!rng.gen_bool(-3.0));
+            assert!(rng.gen_bool(7.0));
+            assert!(!rng.gen_ratio(0, 5));
+            assert!(rng.gen_ratio(5, 5));
+        }
+        assert!(!Yarrow::deterministic_from(vec![0]).gen_bool(0.0));
+        assert!(Yarrow::deterministic_from(vec![0xFF; 8]).gen_bool(1.0));
+        assert!(Yarrow::deterministic_from(vec![0xFF; 8]).gen_ratio(u32::MAX, u64::from(u32::MAX)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid ratio")]
+    fn test_gen_ratio_invalid() {
+        Yarrow::new(12345).gen_ratio(1, 0);
+    }
+
+    #[test]
+    fn test_generate_f32() {
+        let mut rng = Yarrow::new(12345);
+        let samples = 10_000;
+        let mut sum = 0.0;
+        for _ in 0..samples {
+            let value = rng.generate_f32();
+            assert!((0.0..1.0).contains(&value), "La valeur doit être dans [0, 1)");
+            sum += f64::from(value);
+        }
+        let mean = sum / samples as f64;
+        assert!((mean - 0.5).abs() < 0.02, "Moyenne trop éloignée de 0.5 : {}", mean);
+        assert_eq!(Yarrow::deterministic_from(vec![0xFF; 8]).generate_f32(), 1.0 - 2f32.powi(-24));
+    }
+
+    #[test]
+    fn test_choose() {
+        let mut rng = Yarrow::new(12345);
+        let empty: [u32; 0] = [];
+        assert_eq!(rng.choose(&empty), None);
+        assert_eq!(rng.choose_mut(&mut []), None::<&mut u32>);
+        for _ in 0..10 {
+            assert_eq!(rng.choose(&[7]), Some(&7), "Un seul élément doit toujours être choisi");
+        }
+
+        let items = [0usize, 1, 2, 3];
+        let samples = 4000;
+        let mut counts = [0usize; 4];
+        for _ in 0..samples {
+            counts[*rng.choose(&items).unwrap()] += 1;
+        }
+        // Each count is about 1000 with a standard deviation of about 27.
+        assert!(counts.iter().all(|&count| (850..1150).contains(&count)), "Répartition non uniforme : {:?}", counts);
+
+        let mut values = [0u32; 3];
+        *rng.choose_mut(&mut values).unwrap() += 1;
+        assert_eq!(values.iter().sum::<u32>(), 1);
+    }
+
+    #[test]
+    fn test_choose_multiple() {
+        let mut rng = Yarrow::new(12345);
+        let items: Vec<u32> = (0..20).collect();
+        for k in [0, 1, 5, 19, 20, 25] {
+            let chosen = rng.choose_multiple(&items, k);
+            assert_eq!(chosen.len(), k.min(items.len()), "Nombre d'éléments incorrect pour k = {}", k);
+            assert!(chosen.iter().all(|value| items.contains(value)), "Élément absent de l'entrée");
+            let distinct: BTreeSet<u32> = chosen.iter().copied().collect();
+            assert_eq!(distinct.len(), chosen.len(), "Un élément apparaît deux fois");
+        }
+
+        // Every element of a 4-element slice is picked in a 2-subset half of the time.
+        let mut counts = [0usize; 4];
+        for _ in 0..2000 {
+            for value in rng.choose_multiple(&[0usize, 1, 2, 3], 2) {
+                counts[value] += 1;
+            }
+        }
+        assert!(counts.iter().all(|&count| (850..1150).contains(&count)), "Répartition non uniforme : {:?}", counts);
+    }
+
+    #[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+    #[test]
+    fn test_thread_yarrow() {
+        let handles: Vec<_> = (0..4)
+            .map(|_| std::thread::spawn(|| {
+                let rng = thread_yarrow();
+                let mut buf = [0u8; 8];
+                rng.fill(&mut buf);
+                assert!(rng.generate_bounded_number(1, 6) <= 6);
+                (0..50).map(|_| rng.next_u64()).collect::<Vec<u64>>()
+            }))
+            .collect();
+        let sequences: Vec<Vec<u64>> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+
+        for (i, a) in sequences.iter().enumerate() {
+            for b in &sequences[i + 1..] {
+                assert_ne!(a, b, "Deux threads produisent la même séquence");
+            }
+        }
+    }
+
+    #[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+    #[test]
+    fn test_default_and_random() {
+        let mut a = Yarrow::default();
+        let mut b = Yarrow::default();
+        let first: Vec<u64> = (0..10).map(|_| a.generate_random_number()).collect();
+        let second: Vec<u64> = (0..10).map(|_| b.generate_random_number()).collect();
+        assert_ne!(first, second, "Deux générateurs par défaut produisent la même séquence");
+
+        let values: BTreeSet<u64> = (0..100).map(|_| random::<u64>()).collect();
+        assert_eq!(values.len(), 100, "random::<u64>() a répété une valeur");
+        assert!((0..100).map(|_| random::<f64>()).all(|value| (0.0..1.0).contains(&value)));
+        let heads = (0..1000).filter(|_| random::<bool>()).count();
+        assert!((400..600).contains(&heads), "Proportion de true anormale : {}", heads);
+        let _: (u8, i32, isize, f32) = (random(), random(), random(), random());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_thread_yarrow_shared() {
+        let shared = ThreadYarrow::new(Yarrow::new(12345));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let handle = shared.clone();
+                std::thread::spawn(move || (0..50).map(|_| handle.next_u64()).collect::<Vec<u64>>())
+            })
+            .collect();
+        let draws: Vec<u64> = handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect();
+
+        let distinct: BTreeSet<u64> = draws.iter().copied().collect();
+        assert_eq!(distinct.len(), draws.len(), "Le générateur partagé a répété une valeur");
+        assert_eq!(shared.yarrow.lock().unwrap().draw_count(), 200);
+    }
+
+    #[test]
+    fn test_generate_range() {
+        fn check<T: RangeInt>(rng: &mut Yarrow, min: T, max: T) {
+            for _ in 0..200 {
+                let value = rng.generate_range(min, max);
+                assert!(min <= value && value <= max, "{:?} hors de [{:?}, {:?}]", value, min, max);
+            }
+        }
+
+        let mut rng = Yarrow::new(12345);
+        check(&mut rng, 1u8, 6);
+        check(&mut rng, 250u8, u8::MAX);
+        check(&mut rng, 0u8, u8::MAX);
+        check(&mut rng, 1000u16, u16::MAX);
+        check(&mut rng, 0u16, u16::MAX);
+        check(&mut rng, 0u32, 2);
+        check(&mut rng, u32::MAX - 1, u32::MAX);
+        check(&mut rng, 0u64, u64::MAX);
+        check(&mut rng, u64::MAX - 3, u64::MAX);
+        check(&mut rng, 10usize, 20);
+        check(&mut rng, 0usize, usize::MAX);
+        assert_eq!(rng.generate_range(7u16, 7), 7);
+
+        // Every value of a range that does not divide 256 is reachable and roughly uniform.
+        let mut counts = [0usize; 3];
+        for _ in 0..3000 {
+            counts[rng.generate_range(0u8, 2) as usize] += 1;
+        }
+        assert!(counts.iter().all(|&count| (850..1150).contains(&count)), "Répartition non uniforme : {:?}", counts);
+    }
+
+    #[test]
+    fn test_generate_range_signed_and_wide() {
+        let mut rng = Yarrow::new(12345);
+        let mut seen = BTreeSet::new();
+        for _ in 0..1000 {
+            let value = rng.generate_range(-3i32, 3);
+            assert!((-3..=3).contains(&value), "Valeur hors de l'intervalle : {}", value);
+            seen.insert(value);
+        }
+        assert_eq!(seen.len(), 7, "Toutes les valeurs signées doivent apparaître");
+
+        for _ in 0..100 {
+            let value = rng.generate_range(i64::MIN, -1);
+            assert!(value < 0);
+            let wide = rng.generate_range(u128::MAX - 1000, u128::MAX);
+            assert!(wide >= u128::MAX - 1000);
+            let huge = rng.generate_range(1u128 << 100, (1u128 << 100) * 3);
+            assert!(((1u128 << 100)..=(1u128 << 100) * 3).contains(&huge));
+            let signed_wide = rng.generate_range(i128::MIN, i128::MIN + 5);
+            assert!((i128::MIN..=i128::MIN + 5).contains(&signed_wide));
+        }
+        rng.generate_range(i8::MIN, i8::MAX);
+        rng.generate_range(0u128, u128::MAX);
+        assert_eq!(rng.generate_range(-7isize, -7), -7);
+        assert_eq!(rng.try_generate_range(3i16, -3), Err(PrngError::InvalidRange));
+
+        // A single byte is drawn for an i8, as for a u8: 0x00 maps to i8::MIN.
+        assert_eq!(Yarrow::deterministic_from(vec![0x00]).generate_range(i8::MIN, i8::MAX), i8::MIN);
+        assert_eq!(Yarrow::deterministic_from(vec![0xFF]).generate_range(i8::MIN, i8::MAX), i8::MAX);
+
+        // A rejected 128-bit value is replaced without reading the next two words of the stream.
+        let span = (1u128 << 127) + 1;
+        let next = u64::MAX - 5;
+        let mut script = vec![0u8; 16];
+        script.extend(next.to_be_bytes());
+        let mut scripted = Yarrow::deterministic_from_with_policy(script, ExhaustedPolicy::Error);
+        assert!(scripted.generate_range(0, span - 1) < span);
+        assert_eq!(scripted.draw_count(), 2);
+        assert_eq!(scripted.generate_random_number(), next, "Le tirage suivant du flux doit rester aligné");
+
+        // 129 signed values: the same rejected tail as `test_generate_range_narrow_uniform`.
+        let mut rng = Yarrow::new_deterministic(7);
+        let samples = 129 * 50;
+        let mut counts = [0usize; 129];
+        for _ in 0..samples {
+            counts[(i16::from(rng.generate_range(i8::MIN, 0)) + 128) as usize] += 1;
+        }
+        let expected = samples as f64 / 129.0;
+        let chi_squared: f64 = counts.iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+        // 128 degrees of freedom: 219 is the critical value at p = 1e-6.
+        assert!(chi_squared < 219.0, "Distribution biaisée : chi² = {} pour {:?}", chi_squared, counts);
+    }
+
+    #[test]
+    fn test_generate_range_narrow_uniform() {
+        // 129 values: almost half of the byte draws fall in the rejected tail.
+        let mut rng = Yarrow::new_deterministic(7);
+        let samples = 129 * 100;
+        let mut counts = [0usize; 129];
+        for _ in 0..samples {
+            counts[usize::from(rng.generate_range(0u8, 128))] += 1;
+        }
+
+        let expected = samples as f64 / 129.0;
+        let chi_squared: f64 = counts.iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+        // 128 degrees of freedom: 219 is the critical value at p = 1e-6.
+        assert!(chi_squared < 219.0, "Distribution biaisée : chi² = {} pour {:?}", chi_squared, counts);
+    }
+
+    #[test]
+    fn test_generate_range_narrow_draw() {
+        // A `u8` draw reads a single byte, and the biased tail of 256 mod 3 = 1 value is rejected.
+        assert_eq!(Yarrow::deterministic_from(vec![5]).generate_range(0u8, 2), 2);
+        let mut rng = Yarrow::deterministic_from_with_policy(vec![0, 9], ExhaustedPolicy::Error);
+        assert!(rng.generate_range(0u8, 2) <= 2);
+        assert_eq!(rng.generate_range(0u8, 255), 9, "L'octet rejeté ne doit pas décaler le flux");
+        assert_eq!(rng.draw_count(), 2, "Un rejet ne doit pas consommer de tirage supplémentaire");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid range")]
+    fn test_generate_range_inverted_bounds() {
+        Yarrow::new(12345).generate_range(5u32, 4);
+    }
+
+    #[test]
+    fn test_wipe() {
+        let mut rng = Yarrow::new(12345);
+        rng.next_byte();
+        assert!(rng.pool.iter().any(|&byte| byte != 0));
+
+        rng.wipe();
+        assert_eq!(rng.seed, 0, "La graine n'a pas été effacée");
+        assert!(rng.pool.iter().all(|&byte| byte == 0), "Le pool n'a pas été effacé");
+        assert!(rng.buffer.iter().all(|&byte| byte == 0), "Le tampon n'a pas été effacé");
+
+        let mut secret = vec![0xAAu8; 32];
+        wipe_bytes(&mut secret);
+        assert_eq!(secret, vec![0u8; 32]);
+        drop(Yarrow::new(12345));
+    }
+
+    #[test]
+    fn test_reseed_interval() {
+        let mut rng = Yarrow::new_deterministic(12345);
+        rng.generate_random_bytes(10);
+        let (mut with_interval, mut without) = rng.tee();
+        with_interval.set_reseed_interval(100);
+
+        with_interval.generate_random_bytes(50);
+        without.generate_random_bytes(50);
+        assert_eq!(with_interval.pool, without.pool, "Le pool ne doit pas changer avant l'intervalle");
+
+        with_interval.generate_random_bytes(60);
+        without.generate_random_bytes(60);
+        assert_ne!(with_interval.pool, without.pool, "Le pool doit changer une fois l'intervalle atteint");
+        assert_ne!(with_interval.seed, without.seed);
+        assert_eq!(with_interval.bytes_since_reseed, 0);
+
+        let mut first = Yarrow::new_deterministic(1);
+        let mut second = Yarrow::new_deterministic(1);
+        first.set_reseed_interval(64);
+        second.set_reseed_interval(64);
+        for _ in 0..3 {
+            assert_eq!(first.generate_random_bytes(100), second.generate_random_bytes(100), "Deux jumeaux déterministes doivent rester identiques");
+        }
+        assert_eq!(first.stats().reseed_count, second.stats().reseed_count);
+        assert!(first.stats().reseed_count >= 3, "L'intervalle doit avoir déclenché des réensemencements");
+    }
+
+    #[test]
+    #[should_panic(expected = "Reseed interval must be greater than zero")]
+    fn test_reseed_interval_zero() {
+        Yarrow::new(12345).set_reseed_interval(0);
+    }
+
+    #[test]
+    fn test_add_entropy_bytes() {
+        let mut rng = Yarrow::new_deterministic(12345);
+        let pools = rng.fortuna_pools.clone();
+        rng.add_entropy_bytes(&[0x5A; 64]);
+        assert_ne!(rng.fortuna_pools, pools, "L'injection de 64 octets n'a pas modifié le pool");
+        assert_eq!(rng.fortuna_bits[0], 512);
+        assert_eq!(rng.entropy_estimate, 0, "L'entropie ne doit être comptée qu'au drainage de l'accumulateur");
+
+        let mut first = Yarrow::new_deterministic(12345);
+        let mut second = Yarrow::new_deterministic(12345);
+        first.add_entropy_bytes(b"hardware rng output");
+        second.add_entropy_bytes(b"hardware rng output");
+        assert_eq!(first.fortuna_pools, second.fortuna_pools, "Les mêmes octets doivent donner le même état");
+
+        let mut wrapped = Yarrow::new_deterministic(12345);
+        let mut direct = Yarrow::new_deterministic(12345);
+        wrapped.add_entropy(67890);
+        direct.add_entropy_bytes(&67890u64.to_be_bytes());
+        assert_eq!(wrapped.fortuna_pools, direct.fortuna_pools);
+        assert_eq!(wrapped.fortuna_bits[0], 64);
+    }
+
+    #[test]
+    fn test_reseed_bytes() {
+        let mut first = Yarrow::new_deterministic(12345);
+        let mut second = Yarrow::new_deterministic(12345);
+        first.set_time_counter(3600);
+        second.set_time_counter(3600);
+        first.reseed_bytes(&[7; 64]);
+        second.reseed_bytes(&[7; 64]);
+        assert_eq!((first.seed, &first.pool), (second.seed, &second.pool), "Les mêmes octets doivent donner le même état");
+        assert_ne!(first.seed, 12345, "La graine doit être mise à jour");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_generate_normal() {
+        let mut rng = Yarrow::new(12345);
+        let samples = 20_000;
+        let values: Vec<f64> = (0..samples).map(|_| rng.generate_normal(10.0, 2.0)).collect();
+        assert!(values.iter().all(|value| value.is_finite()));
+
+        let mean = values.iter().sum::<f64>() / samples as f64;
+        let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / (samples - 1) as f64;
+        // The standard errors of the mean and of the standard deviation are about 0.014 and 0.01.
+        assert!((mean - 10.0).abs() < 0.1, "Moyenne incorrecte : {}", mean);
+        assert!((variance.sqrt() - 2.0).abs() < 0.1, "Écart type incorrect : {}", variance.sqrt());
+
+        // A zero uniform draw must not reach ln(0).
+        assert_eq!(Yarrow::deterministic_from(vec![0; 16]).generate_normal(10.0, 2.0), 10.0);
+    }
+
+    #[test]
+    fn test_generate_token() {
+        let mut rng = Yarrow::new(12345);
+        let token = rng.generate_token(32);
+        assert_eq!(token.len(), 32);
+        assert!(token.bytes().all(|byte| TOKEN_ALPHABET.contains(&byte)), "Caractère hors de l'alphabet : {}", token);
+        assert_ne!(token, rng.generate_token(32), "Deux jetons identiques");
+        assert_eq!(rng.generate_token(0), "");
+
+        let pin = rng.generate_token_from_alphabet(100, b"0123456789");
+        assert_eq!(pin.len(), 100);
+        assert!(pin.bytes().all(|byte| byte.is_ascii_digit()));
+        assert_eq!(rng.generate_token_from_alphabet(5, b"x"), "xxxxx");
+    }
+
+    #[test]
+    #[should_panic(expected = "Alphabet must not be empty")]
+    fn test_generate_token_empty_alphabet() {
+        Yarrow::new(12345).generate_token_from_alphabet(8, b"");
+    }
+
+    #[test]
+    fn test_generate_uuid_v4() {
+        let mut rng = Yarrow::new(12345);
+        for _ in 0..100 {
+            let uuid = rng.generate_uuid_v4();
+            assert_eq!(uuid[6] >> 4, 4, "Version incorrecte");
+            assert_eq!(uuid[8] >> 6, 0b10, "Variante incorrecte");
+        }
+        assert_ne!(rng.generate_uuid_v4(), rng.generate_uuid_v4(), "Deux UUID identiques");
+
+        let uuid = Yarrow::deterministic_from(vec![0xFF; 16]).generate_uuid_v4_string();
+        assert_eq!(uuid, "ffffffff-ffff-4fff-bfff-ffffffffffff");
+        let text = rng.generate_uuid_v4_string();
+        assert_eq!(text.len(), 36);
+        assert_eq!(&text[14..15], "4");
+    }
+
+    #[test]
+    fn test_new_deterministic_ignores_clock() {
+        let mut first = Yarrow::new_deterministic(12345);
+        let mut second = Yarrow::new_deterministic(12345);
+        first.reseed(67890);
+        let first_bytes = first.generate_random_bytes(1024);
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        second.reseed(67890);
+        assert_eq!(first_bytes, second.generate_random_bytes(1024), "Le flux déterministe dépend de l'horloge");
+        assert_eq!(first.last_reseed_time, 0, "L'horloge système a été lue");
     }
 
-    /// Reseeds the Yarrow generator with new entropy, combining external entropy and current system time.
-    ///
-    /// # Parameters
-    ///
-    /// - `new_seed`: A 64-bit unsigned integer serving as the new seed for reseeding.
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// let mut yarrow_instance = Yarrow::new(42);
-    /// yarrow_instance.reseed(123);
-    /// ```
-    fn reseed(&mut self, new_seed: u64) {
-        let external_entropy = new_seed;
+    #[test]
+    fn test_mock_clock_reseed_window_elapsed() {
+        let secs = Arc::new(AtomicU64::new(1000));
+        let mut rng = Yarrow::with_clock(12345, Box::new(MockClock { secs: Arc::clone(&secs) }));
+        rng.reseed(67890);
+        assert_eq!(rng.last_reseed_time, 1000, "L'horloge injectée n'a pas été lue");
+        let seed = rng.seed;
 
-        self.add_entropy(external_entropy);
+        secs.store(1061, Ordering::SeqCst);
+        rng.reseed(67890);
+        assert_ne!(rng.seed, seed, "La graine doit changer après 60 secondes");
+        assert_eq!(rng.last_reseed_time, 1061);
+    }
 
-        let combined_entropy = self.combine_entropy();
-        self.mix_entropy(combined_entropy);
+    #[test]
+    fn test_mock_clock_reseed_window_not_elapsed() {
+        let secs = Arc::new(AtomicU64::new(1000));
+        let mut rng = Yarrow::with_clock(12345, Box::new(MockClock { secs: Arc::clone(&secs) }));
+        rng.reseed(67890);
+        let seed = rng.seed;
 
-        let current_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-        if current_time - self.last_reseed_time > 60 {
-            self.last_reseed_time = current_time;
-            self.seed ^= new_seed;
+        secs.store(1060, Ordering::SeqCst);
+        rng.reseed(67890);
+        assert_eq!(rng.seed, seed, "La graine ne doit pas changer dans la fenêtre de 60 secondes");
+        assert_eq!(rng.last_reseed_time, 1000);
+
+        let mut built = YarrowBuilder::new(12345).clock(Box::new(MockClock { secs })).build();
+        built.reseed(67890);
+        assert_eq!(built.last_reseed_time, 1060, "Le constructeur n'utilise pas l'horloge injectée");
+    }
+
+    #[test]
+    fn test_health_test_trips_on_stuck_output() {
+        let mut rng = Yarrow::with_mixer(12345, Box::new(StuckMixer));
+        assert_eq!(rng.try_generate_random_bytes(64), Err(PrngError::HealthTestFailed), "Une sortie bloquée doit être détectée");
+
+        let mut across_calls = Yarrow::with_mixer(12345, Box::new(StuckMixer));
+        assert!(across_calls.try_generate_random_bytes(8).is_ok());
+        assert_eq!(across_calls.try_generate_random_bytes(8), Err(PrngError::HealthTestFailed), "La répétition entre deux appels doit être détectée");
+    }
+
+    #[test]
+    fn test_health_test_passes_normal_output() {
+        let mut rng = Yarrow::new(12345);
+        for _ in 0..2000 {
+            assert!(rng.try_generate_random_bytes(500).is_ok(), "Le test de santé s'est déclenché sur une sortie normale");
         }
+        assert!(rng.try_generate_random_bytes(3).is_ok());
     }
 
-    /// Combines the current state of the Yarrow generator's entropy pool, seed, and last reseed time.
-    ///
-    /// # Returns
-    ///
-    /// Returns a 64-bit unsigned integer representing the combined entropy.
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// let yarrow_instance = Yarrow::new(42);
-    /// let combined_entropy = yarrow_instance.combine_entropy();
-    /// println!("{}", combined_entropy);
-    /// ```
-    fn combine_entropy(&self) -> u64 {
-        let mut combined_entropy = self.seed;
+    #[test]
+    fn test_fortuna_pools_round_robin() {
+        let mut rng = Yarrow::new_deterministic(12345);
+        for entropy in 0..FORTUNA_POOLS as u64 + 1 {
+            rng.add_entropy(entropy);
+        }
+        assert!(rng.fortuna_pools.iter().all(|accumulator| accumulator.len() == 64), "Chaque pool doit avoir reçu de l'entropie");
+        assert_eq!(rng.next_pool, 1);
 
-        for byte in &self.pool {
-            combined_entropy = combined_entropy.wrapping_mul(33).wrapping_add(u64::from(*byte));
+        for reseed in 1..=8u64 {
+            rng.set_time_counter(reseed * 61);
+            rng.reseed(0);
+            if reseed.is_power_of_two() {
+                let last = reseed.trailing_zeros() as usize;
+                assert!(rng.fortuna_pools[last].is_empty(), "Le pool {} doit être vidé au réensemencement {}", last, reseed);
+                assert!(!rng.fortuna_pools[last + 1].is_empty(), "Le pool {} ne doit pas être vidé au réensemencement {}", last + 1, reseed);
+            }
         }
-        combined_entropy ^= self.last_reseed_time;
-        combined_entropy
     }
 
-    /// Mixes additional entropy into the Yarrow generator's entropy pool using the SHA3-512 hashing algorithm.
-    ///
-    /// # Parameters
-    ///
-    /// - `entropy`: A 64-bit unsigned integer representing the additional entropy to be mixed.
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// let mut yarrow_instance = Yarrow::new(42);
-    /// let additional_entropy = 123;
-    /// yarrow_instance.mix_entropy(additional_entropy);
-    /// ```
-    fn mix_entropy(&mut self, entropy: u64) {
-        let entropy_bytes = entropy.to_be_bytes();
+    #[test]
+    fn test_fortuna_late_pool_needs_reseeds() {
+        let mut first = Yarrow::new_deterministic(12345);
+        let mut second = Yarrow::new_deterministic(12345);
+        first.add_entropy(111);
+        second.add_entropy(111);
+        first.add_entropy(222);
+        second.add_entropy(333);
 
-        let mut hasher = Sha3_512::new();
-        hasher.update(&self.pool.make_contiguous());
-        hasher.update(entropy_bytes);
+        first.set_time_counter(61);
+        second.set_time_counter(61);
+        first.reseed(0);
+        second.reseed(0);
+        assert_eq!(first.generate_random_bytes(32), second.generate_random_bytes(32), "Le pool 1 ne doit pas être utilisé au premier réensemencement");
 
-        let hash = hasher.finalize();
-        self.pool = VecDeque::from(hash.as_slice().to_vec());
+        first.set_time_counter(122);
+        second.set_time_counter(122);
+        first.reseed(0);
+        second.reseed(0);
+        assert_ne!(first.generate_random_bytes(32), second.generate_random_bytes(32), "Le pool 1 doit être utilisé au deuxième réensemencement");
     }
 
-    /// Generates a sequence of random bytes using the Yarrow generator.
-    ///
-    /// # Parameters
-    ///
-    /// - `count`: The number of random bytes to generate.
-    ///
-    /// # Returns
-    ///
-    /// Returns a vector of unsigned 8-bit integers representing the generated random bytes.
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// let mut yarrow_instance = Yarrow::new(42);
-    /// let random_bytes = yarrow_instance.generate_random_bytes(16);
-    /// println!("{:?}", random_bytes);
-    /// ```
-    fn generate_random_bytes(&mut self, count: usize) -> Vec<u8> {
-        let mut random_bytes = Vec::with_capacity(count);
+    #[cfg(all(unix, feature = "std", not(feature = "deterministic-only")))]
+    #[test]
+    fn test_reseed_after_fork() {
+        let mut rng = Yarrow::new_deterministic(12345);
+        rng.next_byte();
+        let (mut parent, mut child) = rng.tee();
+        assert_eq!(parent.pid, std::process::id());
+
+        child.reseed_after_fork(parent.pid + 1);
+        assert_ne!(child.seed, parent.seed, "La graine doit changer après un fork");
+        assert_eq!(child.buffer_pos, child.buffer.len(), "Les octets tamponnés avant le fork doivent être jetés");
+
+        // The child now believes it runs in another process, so its next output reseeds it again.
+        let seed = child.seed;
+        child.next_byte();
+        assert_ne!(child.seed, seed, "Le changement de PID doit déclencher un réensemencement");
+        assert_eq!(child.pid, std::process::id());
+        assert_ne!(child.generate_random_bytes(32), parent.generate_random_bytes(32), "Le parent et l'enfant produisent le même flux");
+
+        let seed = parent.seed;
+        parent.next_byte();
+        assert_eq!(parent.seed, seed, "Sans fork, aucun réensemencement ne doit avoir lieu");
+    }
+
+    #[test]
+    fn test_iter_u64() {
+        let mut rng = Yarrow::new(12345);
+        let numbers: Vec<u64> = rng.iter_u64().take(100).collect();
+        assert_eq!(numbers.len(), 100);
+        assert!(numbers.iter().any(|&number| number != numbers[0]), "Toutes les valeurs sont identiques");
 
-        for _ in 0..count {
+        let (mut first, mut second) = rng.tee();
+        let streamed: Vec<u64> = first.iter_u64().take(4).collect();
+        let called: Vec<u64> = (0..4).map(|_| second.generate_random_number()).collect();
+        assert_eq!(streamed, called, "L'itérateur doit suivre generate_random_number");
+    }
 
-            let entropy = self.combine_entropy();
-            self.mix_entropy(entropy);
+    #[test]
+    fn test_with_digest() {
+        let mut small = Yarrow::with_digest::<sha3::Sha3_256>(12345);
+        let mut large = Yarrow::with_digest::<sha3::Sha3_384>(12345);
+        let small_bytes = small.generate_random_bytes(256);
+        let large_bytes = large.generate_random_bytes(256);
+        assert_eq!(small.pool.len(), 32, "Le pool doit avoir la taille du condensé");
+        assert_eq!(large.pool.len(), 48, "Le pool doit avoir la taille du condensé");
 
-            let random_byte = (entropy & 0xFF) as u8;
-            random_bytes.push(random_byte);
+        for bytes in [&small_bytes, &large_bytes] {
+            let words: BTreeSet<&[u8]> = bytes.chunks_exact(8).collect();
+            assert_eq!(words.len(), 32, "La sortie se répète");
         }
+        assert_ne!(small.generate_random_bytes(32), small.generate_random_bytes(32));
+        assert_ne!(small_bytes, large_bytes, "Deux condensés différents donnent le même flux");
 
-        let last_byte = random_bytes.last().copied().unwrap_or(0);
-        self.reseed(last_byte as u64);
+        let mut sha3_512 = Yarrow::with_digest::<Sha3_512>(12345);
+        assert_eq!(sha3_512.generate_random_bytes(64), Yarrow::new_deterministic(12345).generate_random_bytes(64));
 
-        random_bytes
+        assert!(Yarrow::from_snapshot(&sha3_512.to_snapshot()).is_ok());
+        assert!(Yarrow::from_snapshot(&Yarrow::with_mixer(12345, Box::new(Sha3Mixer)).to_snapshot()).is_ok());
+        assert_eq!(Yarrow::from_snapshot(&small.to_snapshot()).err(), Some(PrngError::InvalidSnapshot), "Un mélangeur personnalisé ne peut pas être restauré");
+        let custom = Yarrow::with_mixer(12345, Box::new(CountingMixer { calls: Arc::new(AtomicUsize::new(0)) }));
+        assert_eq!(Yarrow::from_snapshot(&custom.to_snapshot()).err(), Some(PrngError::InvalidSnapshot));
     }
 
-    /// Generates a random 64-bit unsigned integer using the Yarrow generator.
-    ///
-    /// # Returns
-    ///
-    /// Returns a 64-bit unsigned integer representing the generated random number.
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// let mut yarrow_instance = Yarrow::new(42);
-    /// let random_number = yarrow_instance.generate_random_number();
-    /// println!("{}", random_number);
-    /// ```
-    fn generate_random_number(&mut self) -> u64 {
-        let random_bytes = self.generate_random_bytes(8);
+    #[test]
+    fn test_weighted_index() {
+        let mut rng = Yarrow::new(12345);
+        for _ in 0..100 {
+            assert_eq!(rng.weighted_index(&[0, 1]), Some(1), "Un poids nul ne doit jamais être tiré");
+        }
 
-        let mut random_number: u64 = 0;
+        let mut counts = [0usize; 3];
+        for _ in 0..30_000 {
+            counts[rng.weighted_index(&[1, 1, 1]).unwrap()] += 1;
+        }
+        for count in counts {
+            assert!((9_000..11_000).contains(&count), "Répartition non uniforme : {:?}", counts);
+        }
 
-        for &byte in &random_bytes {
-            random_number = (random_number << 8) | u64::from(byte);
+        assert_eq!(rng.weighted_index(&[]), None);
+        assert_eq!(rng.weighted_index(&[0, 0, 0]), None);
+        assert_eq!(rng.weighted_index(&[u64::MAX]), Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Total weight overflows u64")]
+    fn test_weighted_index_overflow() {
+        Yarrow::new(12345).weighted_index(&[u64::MAX, 1]);
+    }
+
+    #[test]
+    fn test_generate_bounded_number_ct() {
+        let mut rng = Yarrow::new(12345);
+        let mut counts = [0usize; 10];
+        for _ in 0..5000 {
+            let number = rng.generate_bounded_number_ct(10, 19);
+            assert!((10..=19).contains(&number), "Le nombre généré est hors de la plage spécifiée");
+            counts[(number - 10) as usize] += 1;
         }
+        for count in counts {
+            assert!((350..=650).contains(&count), "Distribution trop déséquilibrée : {:?}", counts);
+        }
+        assert_eq!(rng.generate_bounded_number_ct(7, 7), 7);
+        rng.generate_bounded_number_ct(0, u64::MAX);
 
-        random_number
+        // About half the candidates are rejected with this range and none with the small one, yet
+        // every call hashes the same number of candidates.
+        let mut rng = Yarrow::new_deterministic(12345);
+        let mut hashes = BTreeSet::new();
+        for max in [9, (1 << 63) + 1] {
+            for _ in 0..50 {
+                let before = rng.hash_ops;
+                assert!(rng.generate_bounded_number_ct(0, max) <= max);
+                hashes.insert(rng.hash_ops - before);
+            }
+        }
+        assert_eq!(hashes.len(), 1, "Le nombre de hachages dépend de la valeur tirée");
+
+        // The candidates are independent draws: after three rejected ones, the fourth is used.
+        let mut script = vec![0u8; 24];
+        script.extend([0xFF; 8]);
+        let mut scripted = Yarrow::deterministic_from_with_policy(script, ExhaustedPolicy::Error);
+        assert_eq!(scripted.generate_bounded_number_ct(0, 1 << 63), 1 << 63);
+        assert_eq!(scripted.draw_count(), 4);
     }
 
-    /// Generates a random 64-bit unsigned integer within a specified range using the Yarrow generator.
-    ///
-    /// # Parameters
-    ///
-    /// - `min`: The minimum value of the generated number (inclusive).
-    /// - `max`: The maximum value of the generated number (inclusive).
-    ///
-    /// # Returns
-    ///
-    /// Returns a 64-bit unsigned integer within the specified range.
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// let mut yarrow_instance = Yarrow::new(42);
-    /// let random_number = yarrow_instance.generate_bounded_number(10, 20);
-    /// println!("{}", random_number);
-    /// ```
-    fn generate_bounded_number(&mut self, min: u64, max: u64) -> u64 {
-        let random_number = self.generate_random_number();
+    #[test]
+    fn test_generate_when_seeded() {
+        let mut rng = Yarrow::new(12345);
+        assert!(!rng.is_seeded());
+        assert_eq!(rng.generate_when_seeded(32), Err(PrngError::NotSeeded), "Un générateur non amorcé ne doit rien produire");
+        assert_eq!(rng.draw_count(), 0);
 
-        min + (random_number % (max - min + 1))
+        rng.add_entropy_bytes(&[7; 31]);
+        assert_eq!(rng.generate_when_seeded(32), Err(PrngError::NotSeeded));
+        assert_eq!(rng.entropy_estimate, 248, "L'accumulateur 0 doit avoir été drainé");
+        rng.add_entropy(67890);
+        assert!(!rng.is_seeded(), "L'entropie encore dans les accumulateurs ne doit pas compter");
+        assert_eq!(rng.generate_when_seeded(32).map(|bytes| bytes.len()), Ok(32));
+        assert!(rng.is_seeded(), "39 octets d'entropie drainés doivent suffire");
     }
-}
 
-/// Shuffles the elements of a mutable slice using the Fisher-Yates algorithm with a time-based seed.
-///
-/// # Parameters
-///
-/// - `items`: A mutable slice of elements to be shuffled.
-///
-/// # Examples
-///
-/// ```rust
-/// let mut elements = vec![1, 2, 3, 4, 5];
-/// shuffle(&mut elements);
-/// println!("{:?}", elements);
-/// ```
-fn shuffle<T>(items: &mut [T]) {
-    let len = items.len();
-    for i in (1..len).rev() {
-        let j = (SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos() as usize) % (i + 1);
-        items.swap(i, j);
+    #[test]
+    fn test_generate_when_seeded_uses_entropy() {
+        let (mut first, mut second) = Yarrow::new_deterministic(12345).tee();
+        first.add_entropy_bytes(&[0x11; 32]);
+        second.add_entropy_bytes(&[0x22; 32]);
+        assert!(!first.is_seeded() && !second.is_seeded());
+
+        let first_output = first.generate_when_seeded(16).expect("32 octets d'entropie doivent suffire");
+        let second_output = second.generate_when_seeded(16).expect("32 octets d'entropie doivent suffire");
+        assert_ne!(first_output, second_output, "La première sortie doit dépendre de l'entropie ajoutée");
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::collections::HashMap;
-    use super::*;
+    #[cfg(all(feature = "std", not(feature = "deterministic-only")))]
+    #[test]
+    fn test_from_os_entropy_is_seeded() {
+        let mut rng = Yarrow::from_os_entropy().expect("Entropie du système indisponible");
+        assert!(rng.is_seeded());
+        assert!(rng.generate_when_seeded(16).is_ok());
+
+        let mut rng = Yarrow::new_seeded().expect("Entropie du système indisponible");
+        assert!(rng.is_seeded());
+        assert!(rng.generate_when_seeded(16).is_ok());
+    }
 
     #[test]
-    fn test_add_entropy() {
+    fn test_fork_stream() {
+        let mut master = Yarrow::new_deterministic(12345);
+        master.generate_random_bytes(16);
+        let first = master.fork_stream(1).generate_random_bytes(64);
+        let second = master.fork_stream(2).generate_random_bytes(64);
+        assert_ne!(first, second, "Deux flux différents produisent la même sortie");
+        assert_eq!(first, master.fork_stream(1).generate_random_bytes(64), "La dérivation n'est pas reproductible");
+
+        let (mut copy, _) = master.tee();
+        assert_eq!(copy.fork_stream(2).generate_random_bytes(64), second, "Une copie de l'état doit donner les mêmes flux");
+        let master_bytes = master.generate_random_bytes(64);
+        assert_ne!(master_bytes, first);
+        assert_ne!(master.fork_stream(1).generate_random_bytes(64), first, "Le flux doit dépendre de l'état du maître");
+    }
+
+    #[test]
+    fn test_partial_shuffle() {
         let mut rng = Yarrow::new(12345);
-        let initial_state = rng.pool.clone();
-        rng.add_entropy(67890);
-        assert_ne!(rng.pool, initial_state, "L'ajout d'entropie n'a pas modifié l'état du générateur");
+        let mut items: Vec<u32> = (0..100).collect();
+        let (prefix, rest) = rng.partial_shuffle(&mut items, 5);
+        assert_eq!(prefix.len(), 5);
+        assert_eq!(rest.len(), 95);
+        let mut sorted = items.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..100).collect::<Vec<u32>>(), "Des éléments ont été perdus");
+
+        let prefixes: BTreeSet<Vec<u32>> = (0..20)
+            .map(|_| {
+                let mut items: Vec<u32> = (0..100).collect();
+                rng.partial_shuffle(&mut items, 5).0.to_vec()
+            })
+            .collect();
+        assert!(prefixes.len() > 1, "Le préfixe n'est pas mélangé");
+
+        let mut small = vec![1, 2, 3];
+        let (prefix, rest) = rng.partial_shuffle(&mut small, 10);
+        assert_eq!((prefix.len(), rest.len()), (3, 0));
+        assert!(rng.partial_shuffle(&mut Vec::<u32>::new(), 3).0.is_empty());
     }
 
     #[test]
-    fn test_reseed() {
+    fn test_try_errors() {
         let mut rng = Yarrow::new(12345);
-        let initial_state = rng.pool.clone();
-        rng.reseed(67890);
-        assert_ne!(rng.pool, initial_state, "La méthode reseed n'a pas modifié l'état du générateur");
+        assert_eq!(rng.try_generate_bounded_number(20, 10), Err(PrngError::InvalidRange));
+        assert_eq!(rng.try_generate_range(20u16, 10), Err(PrngError::InvalidRange));
+        assert!(rng.try_generate_range(10u16, 20).is_ok_and(|number| (10..=20).contains(&number)));
+
+        assert_eq!(rng.try_weighted_index(&[]), Err(PrngError::EmptyInput));
+        assert_eq!(rng.try_weighted_index(&[0, 0]), Err(PrngError::EmptyInput));
+        assert_eq!(rng.try_weighted_index(&[u64::MAX, 1]), Err(PrngError::Overflow));
+        assert_eq!(rng.try_weighted_index(&[0, 3]), Ok(1));
     }
 
+    #[cfg(all(feature = "std", not(feature = "deterministic-only")))]
     #[test]
-    fn test_generate_random_bytes() {
+    fn test_clock_error() {
+        let before_epoch = UNIX_EPOCH - std::time::Duration::from_secs(1);
+        assert_eq!(secs_since_epoch(before_epoch), Err(PrngError::ClockError));
+        assert_eq!(secs_since_epoch(UNIX_EPOCH + std::time::Duration::from_secs(90)), Ok(90));
+        assert!(SystemClock.try_now_secs().is_ok());
+    }
+
+    #[test]
+    fn test_gen_in() {
         let mut rng = Yarrow::new(12345);
-        let first = rng.generate_random_bytes(10);
-        let second = rng.generate_random_bytes(10);
-        assert_ne!(first, second, "Les deux appels à generate_random_bytes ont produit les mêmes résultats");
+        let mut seen = BTreeSet::new();
+        for _ in 0..1000 {
+            let half_open = rng.gen_in(0..10);
+            assert!(half_open < 10, "La borne supérieure exclue a été tirée");
+            seen.insert(half_open);
+            assert!((5..=7).contains(&rng.gen_in(5..=7)));
+            assert!(rng.gen_in(u64::MAX - 2..) >= u64::MAX - 2);
+            assert!(rng.gen_in(..3) < 3);
+            assert!(rng.gen_in(..=3) <= 3);
+        }
+        assert_eq!(seen.len(), 10, "Toutes les valeurs de 0..10 doivent apparaître");
+        assert_eq!(rng.gen_in(4..5), 4);
+        assert_eq!(rng.gen_in(u64::MAX..), u64::MAX);
+        rng.gen_in(..);
+        rng.gen_in((Bound::Excluded(0), Bound::Unbounded));
+    }
+
+    #[test]
+    #[should_panic(expected = "Empty range")]
+    fn test_gen_in_empty() {
+        Yarrow::new(12345).gen_in(5..5);
+    }
+
+    #[test]
+    fn test_debug_redacts_state() {
+        let seed = 0x1234_5678_9ABC_DEF0;
+        let mut rng = Yarrow::new_deterministic(seed);
+        rng.generate_random_bytes(16);
+        let debug = format!("{:?}", rng);
+        assert!(debug.starts_with("Yarrow { pool_len: 64"), "Format inattendu : {}", debug);
+        for secret in [rng.seed, seed] {
+            assert!(!debug.contains(&secret.to_string()), "La graine apparaît dans {}", debug);
+            assert!(!debug.to_lowercase().contains(&format!("{:x}", secret)), "La graine apparaît dans {}", debug);
+        }
+        assert!(!debug.contains("pool:"), "Le contenu du pool apparaît dans {}", debug);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_add_entropy_from_reader() {
+        struct TrickleReader {
+            data: Vec<u8>,
+            calls: usize,
+        }
+
+        impl std::io::Read for TrickleReader {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                self.calls += 1;
+                if self.calls.is_multiple_of(4) {
+                    return Err(std::io::Error::from(std::io::ErrorKind::Interrupted));
+                }
+                let count = buf.len().min(3).min(self.data.len());
+                buf[..count].copy_from_slice(&self.data[..count]);
+                self.data.drain(..count);
+                Ok(count)
+            }
+        }
+
+        struct BrokenReader;
+
+        impl std::io::Read for BrokenReader {
+            fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::from(std::io::ErrorKind::BrokenPipe))
+            }
+        }
+
+        let data: Vec<u8> = (0..=255).collect();
+        let mut rng = Yarrow::new_deterministic(12345);
+        let pools = rng.fortuna_pools.clone();
+        let mut cursor: &[u8] = &data;
+        assert_eq!(rng.add_entropy_from_reader(&mut cursor, 100).unwrap(), 100);
+        assert_ne!(rng.fortuna_pools, pools, "Le pool n'a pas été modifié");
+        assert_eq!(cursor.len(), 156, "Plus d'octets que demandé ont été lus");
+
+        let mut direct = Yarrow::new_deterministic(12345);
+        direct.add_entropy_bytes(&data[..100]);
+        assert_eq!(rng.fortuna_pools, direct.fortuna_pools, "La lecture en flux doit équivaloir à add_entropy_bytes");
+        assert_eq!(rng.fortuna_bits, direct.fortuna_bits);
+        assert_eq!(rng.fortuna_bits[0], 800);
+
+        let mut trickle = Yarrow::new_deterministic(12345);
+        let mut reader = TrickleReader { data: data[..100].to_vec(), calls: 0 };
+        assert_eq!(trickle.add_entropy_from_reader(&mut reader, 1000).unwrap(), 100, "La fin du flux doit être gérée");
+        assert_eq!(trickle.fortuna_pools, direct.fortuna_pools, "Les lectures partielles doivent être reprises");
+
+        let mut failing = Yarrow::new_deterministic(12345);
+        let mut reader = std::io::Read::chain(&b"abc"[..], BrokenReader);
+        assert!(failing.add_entropy_from_reader(&mut reader, 10).is_err(), "L'erreur de lecture doit être propagée");
+        assert_eq!(failing.fortuna_pools, pools, "Rien ne doit être absorbé en cas d'erreur");
+    }
+
+    #[test]
+    fn test_split() {
+        let mut parent = Yarrow::new_deterministic(12345);
+        let (mut unsplit, mut again) = parent.tee();
+        let mut child = parent.split();
+        let mut same_child = again.split();
+
+        let parent_output = parent.generate_random_bytes(32);
+        let child_output = child.generate_random_bytes(32);
+        assert_ne!(parent_output, child_output, "Le parent et l'enfant produisent la même sortie");
+        assert_ne!(parent_output, unsplit.generate_random_bytes(32), "Le parent doit avancer lors de la séparation");
+        assert_eq!(again.generate_random_bytes(32), parent_output, "La séparation doit être déterministe");
+        assert_eq!(same_child.generate_random_bytes(32), child_output);
+
+        let mut grandchild = child.split();
+        assert_ne!(grandchild.generate_random_bytes(32), child.generate_random_bytes(32));
+    }
+
+    #[test]
+    fn test_children_keep_configuration() {
+        let mut parent = Yarrow::with_digest::<sha3::Sha3_256>(12345);
+        parent.reseed_bytes(&[0x5A; 32]);
+        let mut children = vec![parent.split(), parent.fork_stream(7)];
+        children.extend(parent.split_streams(2));
+        for child in &mut children {
+            // Every child starts from a 64-byte derived state, enough to back the inherited estimate.
+            assert_eq!(child.pool.len(), 64, "L'enfant doit partir d'un état complet");
+            assert_eq!(child.entropy_estimate, parent.entropy_estimate, "L'enfant doit garder l'estimation d'entropie");
+            assert_eq!(child.time_counter, parent.time_counter);
+            child.generate_random_bytes(16);
+            assert_eq!(child.pool.len(), 32, "L'enfant doit garder le mélangeur");
+        }
+
+        let mut clocked = Yarrow::with_clock(12345, Box::new(MockClock { secs: Arc::new(AtomicU64::new(100)) }));
+        let mut children = vec![clocked.split(), clocked.fork_stream(7)];
+        children.extend(clocked.split_streams(2));
+        assert!(children.iter().all(|child| child.clock.is_some() && child.time_counter.is_none()), "L'enfant doit garder l'horloge");
+    }
+
+    #[test]
+    fn test_add_entropy_from_source() {
+        let mut rng = Yarrow::new_deterministic(12345);
+        let state = rng.pool.clone();
+
+        // Inputs alternate fast, slow, fast: 2 x 60 bits reach the fast threshold on the third one.
+        rng.add_entropy_from_source(0, &[1; 8], 60);
+        rng.add_entropy_from_source(0, &[2; 8], 60);
+        assert_eq!(rng.stats().reseed_count, 0, "Réensemencement rapide prématuré");
+        assert_eq!(rng.pool, state, "L'état ne doit pas changer avant le seuil");
+        rng.add_entropy_from_source(0, &[3; 8], 60);
+        assert_eq!(rng.stats().reseed_count, 1, "Le pool rapide doit déclencher un réensemencement");
+        assert_ne!(rng.pool, state);
+        assert!(rng.fast_pool.is_empty() && !rng.slow_pool.is_empty());
+
+        // The estimate is capped at 8 bits per byte.
+        rng.add_entropy_from_source(1, &[4; 2], 1000);
+        assert_eq!(rng.entropy_sources[&1].fast_bits, 16);
+
+        // One source alone never triggers a slow reseed, two do.
+        let mut slow = Yarrow::new_deterministic(12345);
+        for byte in 0..4 {
+            slow.add_entropy_from_source(0, &[byte; 32], 90);
+        }
+        assert_eq!(slow.entropy_sources[&0].slow_bits, 180);
+        let fast_reseeds = slow.stats().reseed_count;
+        slow.add_entropy_from_source(1, &[7; 32], 20);
+        slow.add_entropy_from_source(1, &[8; 32], 200);
+        assert_eq!(slow.stats().reseed_count, fast_reseeds + 1, "Deux sources doivent déclencher le pool lent");
+        assert!(slow.slow_pool.is_empty() && slow.fast_pool.is_empty());
+        assert!(slow.entropy_sources.values().all(|estimate| estimate.fast_bits == 0 && estimate.slow_bits == 0));
+
+        let mut pending = Yarrow::new_deterministic(12345);
+        pending.add_entropy_from_source(9, &[5; 4], 8);
+        pending.add_entropy_from_source(9, &[6; 4], 8);
+        let mut restored = Yarrow::from_snapshot(&pending.to_snapshot()).unwrap();
+        assert_eq!((&restored.fast_pool, &restored.slow_pool), (&pending.fast_pool, &pending.slow_pool));
+        assert_eq!(restored.entropy_sources, pending.entropy_sources, "Les estimations par source doivent être restaurées");
+        assert_eq!(restored.generate_random_bytes(16), pending.generate_random_bytes(16));
+    }
+
+    #[test]
+    fn test_horizon_rng() {
+        fn roll<R: HorizonRng + ?Sized>(rng: &mut R) -> u64 {
+            rng.generate_bounded_number(1, 6)
+        }
+
+        let mut fortuna = crate::fortuna::Fortuna::new();
+        fortuna.reseed(b"seed");
+        let mut generators: Vec<Box<dyn HorizonRng>> = vec![Box::new(Yarrow::new_deterministic(12345)), Box::new(fortuna)];
+        for rng in &mut generators {
+            assert!((0..100).all(|_| (1..=6).contains(&roll(rng.as_mut()))), "Valeur hors de l'intervalle");
+            let before = rng.next_u64();
+            HorizonRng::reseed(rng.as_mut(), b"fresh entropy");
+            assert_ne!(before, rng.next_u64());
+        }
+
+        // The trait sampler reads the same stream as the inherent one.
+        let mut rng = Yarrow::new_deterministic(12345);
+        let mut reference = Yarrow::new_deterministic(12345);
+        assert_eq!(HorizonRng::generate_bounded_number(&mut rng, 10, 20), reference.generate_bounded_number(10, 20));
+
+        let mut unseeded: Box<dyn HorizonRng> = Box::new(crate::fortuna::Fortuna::new());
+        assert_eq!(unseeded.try_generate_bounded_number(1, 6), Err(PrngError::NotSeeded), "Un générateur non initialisé doit renvoyer une erreur");
+        assert_eq!(unseeded.try_generate_bounded_number(6, 1), Err(PrngError::InvalidRange));
+
+        // The default sampler draws again instead of keeping a rejected value.
+        let mut scripted = ReplayRng::from_u64s(&[0, 1 << 63], ExhaustedPolicy::Error);
+        struct Scripted<'a>(&'a mut ReplayRng);
+        impl RandomSource for Scripted<'_> {
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), PrngError> {
+                self.0.try_fill_bytes(dest)
+            }
+        }
+        impl HorizonRng for Scripted<'_> {
+            fn reseed(&mut self, _seed: &[u8]) {}
+        }
+        assert_eq!(Scripted(&mut scripted).generate_bounded_number(0, 1 << 63), 1 << 63);
     }
 
     #[test]
@@ -300,7 +7873,7 @@ mod tests {
             let count = distribution_counts.entry(number).or_insert(0);
             *count += 1;
 
-            assert!(number >= 10 && number <= 20, "Le nombre généré est hors de la plage spécifiée");
+            assert!((10..=20).contains(&number), "Le nombre généré est hors de la plage spécifiée");
         }
 
         // Afficher la répartition des valeurs
@@ -314,7 +7887,7 @@ mod tests {
     fn test_shuffle() {
         let mut items = vec![1, 2, 3, 4, 5];
         let original = items.clone();
-        shuffle(&mut items);
+        shuffle(&mut items, &mut Yarrow::new(12345));
         assert_ne!(items, original, "Les éléments n'ont pas été mélangés");
         items.sort();
         assert_eq!(items, original, "Tous les éléments d'origine ne sont pas présents après le mélange");
@@ -324,9 +7897,36 @@ mod tests {
     fn test_shuffle_string() {
         let mut s = "Hello, World!".chars().collect::<Vec<_>>();
         let original = s.clone().into_iter().collect::<String>();
-        shuffle(&mut s);
+        shuffle(&mut s, &mut Yarrow::new(12345));
         let shuffled = s.into_iter().collect::<String>();
         println!("shuffled: {}", shuffled);
         assert_ne!(shuffled, original, "The string was not shuffled");
     }
+
+    #[test]
+    fn test_shuffle_seeded() {
+        let permute = |rng: &mut dyn HorizonRng| {
+            let mut items: Vec<u32> = (0..20).collect();
+            shuffle(&mut items, rng);
+            items
+        };
+        let first = permute(&mut Yarrow::new_deterministic(12345));
+        assert_eq!(first, permute(&mut Yarrow::new_deterministic(12345)), "Une même graine doit donner le même mélange");
+        assert_ne!(first, permute(&mut Yarrow::new_deterministic(54321)), "Des graines différentes doivent donner des mélanges différents");
+
+        let mut own: Vec<u32> = (0..20).collect();
+        Yarrow::new_deterministic(12345).shuffle(&mut own);
+        assert_eq!(first, own, "shuffle doit suivre l'ordre de Yarrow::shuffle");
+
+        let mut pinned: Vec<u32> = (0..10).collect();
+        shuffle(&mut pinned, &mut Yarrow::new_deterministic(42));
+        assert_eq!(pinned, PINNED_SHUFFLE, "L'ordre du mélange de Fisher-Yates a changé");
+
+        let seeded_fortuna = || {
+            let mut fortuna = crate::fortuna::Fortuna::new();
+            fortuna.reseed(b"graine de test");
+            fortuna
+        };
+        assert_eq!(permute(&mut seeded_fortuna()), permute(&mut seeded_fortuna()), "Fortuna doit aussi mélanger de façon reproductible");
+    }
 }
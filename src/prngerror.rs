@@ -0,0 +1,65 @@
+use core::{
+    error::Error,
+    fmt::{self, Display},
+};
+/// Represents the errors returned by the Yarrow generator and its random sources.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrngError {
+    /// A finite random source has no bytes left to serve.
+    Exhausted,
+    /// A tenant has used up its random byte allowance for the current window.
+    QuotaExceeded,
+    /// A dice notation string is malformed or its total overflows.
+    InvalidNotation,
+    /// A range's lower bound is greater than its upper bound.
+    InvalidRange,
+    /// A state snapshot is truncated, has an unknown version or holds inconsistent values.
+    InvalidSnapshot,
+    /// The continuous health test saw repeated output, so the generator may be stuck.
+    HealthTestFailed,
+    /// The generator has not been credited with enough entropy to produce secret output yet.
+    NotSeeded,
+    /// The system clock could not be read, e.g. because it is set before the Unix epoch.
+    ClockError,
+    /// An input that must contain something, such as a weight list, is empty or all zero.
+    EmptyInput,
+    /// An intermediate result, such as the sum of weights, overflows its integer type.
+    Overflow,
+}
+
+/// Implements the `Error` trait for the custom error type `PrngError`.
+impl Error for PrngError {}
+/// Implements the `Display` trait for the custom error type `PrngError`.
+impl Display for PrngError {
+    /// Formats the error message for display.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to a `fmt::Formatter` used for formatting.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `fmt::Result` indicating the success or failure of the formatting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use horizon::prngerror::PrngError;
+    /// let error = PrngError::Exhausted;
+    /// println!("{}", error);
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PrngError::Exhausted => write!(f, "Random source exhausted"),
+            PrngError::QuotaExceeded => write!(f, "Random byte quota exceeded"),
+            PrngError::InvalidNotation => write!(f, "Invalid dice notation"),
+            PrngError::InvalidRange => write!(f, "Invalid range: min is greater than max"),
+            PrngError::InvalidSnapshot => write!(f, "Invalid generator snapshot"),
+            PrngError::HealthTestFailed => write!(f, "Health test failed: repeated generator output"),
+            PrngError::NotSeeded => write!(f, "Generator not seeded with enough entropy"),
+            PrngError::ClockError => write!(f, "System clock is before the Unix epoch"),
+            PrngError::EmptyInput => write!(f, "Empty input"),
+            PrngError::Overflow => write!(f, "Arithmetic overflow"),
+        }
+    }
+}
@@ -8,6 +8,7 @@ pub struct SystemTrayError {
     /// A human-readable error message describing the nature of the error.
     pub message: String,
     /// An error code indicating the specific type of error.
+    #[allow(dead_code)]
     pub code: i32,
 }
 
@@ -0,0 +1,134 @@
+use alloc::vec::Vec;
+use sha3::{Sha3_512, Digest};
+
+/// Domain separation label for the commitment to a seed.
+const COMMITMENT_LABEL: &[u8] = b"horizon-vrf-commitment";
+/// Domain separation label for the output derived from a seed and an input.
+const OUTPUT_LABEL: &[u8] = b"horizon-vrf-output";
+
+/// Commits to a secret seed, producing the value to publish before any output is derived.
+///
+/// # Parameters
+///
+/// - `seed`: A slice of unsigned 8-bit integers representing the secret seed.
+///
+/// # Returns
+///
+/// Returns the SHA3-512 commitment to the seed as a vector of unsigned 8-bit integers.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::vrf::*;
+/// let commitment = commit(b"secret seed");
+/// println!("{}", hex::encode(commitment));
+/// ```
+pub fn commit(seed: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha3_512::new();
+    hasher.update(COMMITMENT_LABEL);
+    hasher.update(seed);
+    hasher.finalize().to_vec()
+}
+
+/// Derives the output for `input` from a committed seed, together with a proof of correct derivation.
+///
+/// The proof reveals the seed itself: anyone holding the published commitment can then check both
+/// that the seed matches it and that the output was computed from it. This is simpler than an
+/// elliptic-curve VRF but, since the seed becomes public, a commitment must only be used for a
+/// single round (e.g. one lottery draw or one election).
+///
+/// # Parameters
+///
+/// - `seed`: A slice of unsigned 8-bit integers representing the committed secret seed.
+/// - `input`: A slice of unsigned 8-bit integers representing the public input (e.g. a round number).
+///
+/// # Returns
+///
+/// Returns the 64-byte output and the proof.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::vrf::*;
+/// let (output, proof) = prove(b"secret seed", b"round 1");
+/// println!("{} {}", hex::encode(output), hex::encode(proof));
+/// ```
+pub fn prove(seed: &[u8], input: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    (derive_output(seed, input), seed.to_vec())
+}
+
+/// Verifies that `output` was correctly derived from the seed behind `commitment` for `input`.
+///
+/// # Parameters
+///
+/// - `commitment`: The commitment published before the output was derived.
+/// - `input`: The public input the output was derived for.
+/// - `output`: The claimed output.
+/// - `proof`: The proof returned by `prove`.
+///
+/// # Returns
+///
+/// Returns `true` if the proof matches the commitment and the output matches the proof.
+///
+/// # Examples
+///
+/// ```rust
+/// # use horizon::vrf::*;
+/// let commitment = commit(b"secret seed");
+/// let (output, proof) = prove(b"secret seed", b"round 1");
+/// assert!(verify(&commitment, b"round 1", &output, &proof));
+/// ```
+pub fn verify(commitment: &[u8], input: &[u8], output: &[u8], proof: &[u8]) -> bool {
+    commit(proof) == commitment && derive_output(proof, input) == output
+}
+
+/// Hashes a seed and an input into an output.
+///
+/// # Parameters
+///
+/// - `seed`: A slice of unsigned 8-bit integers representing the secret seed.
+/// - `input`: A slice of unsigned 8-bit integers representing the public input.
+///
+/// # Returns
+///
+/// Returns the SHA3-512 output as a vector of unsigned 8-bit integers.
+fn derive_output(seed: &[u8], input: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha3_512::new();
+    hasher.update(OUTPUT_LABEL);
+    hasher.update((seed.len() as u64).to_be_bytes());
+    hasher.update(seed);
+    hasher.update(input);
+    hasher.finalize().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prove_verify() {
+        let seed = b"lottery seed";
+        let commitment = commit(seed);
+        let (output, proof) = prove(seed, b"draw 1");
+
+        assert_eq!(output.len(), 64);
+        assert!(verify(&commitment, b"draw 1", &output, &proof));
+        assert_ne!(output, prove(seed, b"draw 2").0);
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering() {
+        let seed = b"lottery seed";
+        let commitment = commit(seed);
+        let (mut output, proof) = prove(seed, b"draw 1");
+
+        assert!(!verify(&commitment, b"draw 2", &output, &proof));
+        assert!(!verify(&commitment, b"draw 1", &output, b"other seed"));
+
+        let (forged_output, forged_proof) = prove(b"other seed", b"draw 1");
+        assert!(!verify(&commitment, b"draw 1", &forged_output, &forged_proof));
+
+        output[0] ^= 1;
+        assert!(!verify(&commitment, b"draw 1", &output, &proof));
+    }
+}